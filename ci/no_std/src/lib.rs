@@ -0,0 +1,28 @@
+//! Builds `join_it` without its `std` feature, for a target without the standard library:
+//!
+//! ```text
+//! cargo build --manifest-path ci/no_std/Cargo.toml --target thumbv7m-none-eabi
+//! ```
+
+#![no_std]
+
+extern crate join_it;
+
+use join_it::{join_count, Joinable};
+
+
+pub fn matched_keys(v: &[u32], w: &[u32]) -> usize {
+    join_count(v.iter(), w.iter(), |&x| x, |&x| x)
+}
+
+
+pub fn sum_of_pairs(v: &[(u32, u32)], w: &[(u32, u32)]) -> u32 {
+    v.iter().join(w.iter(), |&(k,_)| k, |&(k,_)| k)
+        .map(|(&(_,a), &(_,b))| a + b)
+        .sum()
+}
+
+
+pub fn left_only(v: &[u32], w: &[u32]) -> usize {
+    v.iter().anti_join(w.iter(), |&x| x, |&x| x).count()
+}
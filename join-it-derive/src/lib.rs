@@ -0,0 +1,112 @@
+//! # Derive
+//! `#[derive(HasKey)]` implements `join_it::HasKey` for a struct, using the one field marked with
+//! `#[key]` as the key to join on. The key is cloned out of the item, so the field has to be
+//! `Clone`.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate join_it_derive;
+//! extern crate join_it;
+//!
+//! use join_it::Joinable;
+//!
+//! #[derive(HasKey)]
+//! struct Order {
+//!     #[key]
+//!     id: u64,
+//!     amount: f64,
+//! }
+//!
+//! #[derive(HasKey)]
+//! struct Shipment(#[key] u64, &'static str);
+//!
+//! # fn main() {
+//! let orders = vec![Order { id: 1, amount: 9.5 }, Order { id: 2, amount: 3.0 }];
+//! let shipments = vec![Shipment(2, "posted")];
+//!
+//! let rows = orders.iter().join_by_key(shipments.iter())
+//!     .map(|(o, s)| (o.amount, s.1))
+//!     .collect::<Vec<_>>();
+//! assert_eq!(vec![(3.0, "posted")], rows);
+//! # }
+//! ```
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Error, Fields, Index, Member};
+
+
+#[proc_macro_derive(HasKey, attributes(key))]
+pub fn derive_has_key(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+
+/// Expands the derive for `input`, failing unless exactly one of its fields is marked `#[key]`.
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Err(Error::new_spanned(&input.ident, "HasKey can only be derived for structs")),
+    };
+
+    let mut keys = fields.iter().enumerate().filter(|(_, field)| field.attrs.iter().any(|a| a.path().is_ident("key")));
+    let (index, field) = match keys.next() {
+        Some(key) => key,
+        None => return Err(Error::new_spanned(&input.ident, "HasKey needs a field marked #[key]")),
+    };
+    if let Some((_, other)) = keys.next() {
+        return Err(Error::new_spanned(other, "HasKey needs exactly one field marked #[key]"));
+    }
+
+    let member = match *fields {
+        Fields::Named(_) => Member::Named(field.ident.clone().expect("named field")),
+        _ => Member::Unnamed(Index::from(index)),
+    };
+    let ty = &field.ty;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::join_it::HasKey<#ty> for #name #ty_generics #where_clause {
+            fn key(&self) -> #ty {
+                Clone::clone(&self.#member)
+            }
+        }
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: DeriveInput) -> String {
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn named_and_tuple_fields() {
+        let named = expand(&syn::parse_quote!(struct Order { amount: f64, #[key] id: u64 })).unwrap().to_string();
+        assert!( named.contains("HasKey < u64 > for Order") );
+        assert!( named.contains("self . id") );
+
+        let tuple = expand(&syn::parse_quote!(struct Id(String, #[key] u32);)).unwrap().to_string();
+        assert!( tuple.contains("HasKey < u32 > for Id") );
+        assert!( tuple.contains("self . 1") );
+    }
+
+    #[test]
+    fn exactly_one_key_field() {
+        assert_eq!( "HasKey needs a field marked #[key]", error(syn::parse_quote!(struct Order { id: u64 })) );
+        assert_eq!( "HasKey needs exactly one field marked #[key]", error(syn::parse_quote!(struct Order { #[key] id: u64, #[key] n: u32 })) );
+        assert_eq!( "HasKey can only be derived for structs", error(syn::parse_quote!(enum Order { A })) );
+    }
+}
@@ -0,0 +1,725 @@
+//! Adaptors of the inner join, folding a filtering or mapping step into the join iterator.
+
+use alloc::vec::Vec;
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Keys, Merge, Row};
+use set::{identity, Identity};
+use JoinIt;
+
+
+/// Inner join skipping the rows rejected by a predicate, created by `Joinable::join_filter`.
+pub struct JoinFilter<I, J, KI, KJ, P> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) join: JoinIt<I, J, KI, KJ>,
+    pub(crate) pred: P,
+}
+
+
+impl<I,J,KI,KJ,K,P> Iterator for JoinFilter<I,J,KI,KJ,P> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    P: FnMut(&I::Item, &J::Item) -> bool,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+
+        self.join.find(|(v, w)| pred(v, w))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.join.size_hint().1)
+    }
+}
+
+
+impl<I,J,KI,KJ,K,P> DoubleEndedIterator for JoinFilter<I,J,KI,KJ,P> where
+    I: DoubleEndedIterator,
+    J: DoubleEndedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    P: FnMut(&I::Item, &J::Item) -> bool,
+    K: Ord
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+
+        self.join.rfind(|(v, w)| pred(v, w))
+    }
+}
+
+
+impl<I,J,KI,KJ,P> Clone for JoinFilter<I,J,KI,KJ,P> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    P: Clone
+{
+    fn clone(&self) -> Self {
+        JoinFilter {
+            join: self.join.clone(),
+            pred: self.pred.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,P> FusedIterator for JoinFilter<I,J,KI,KJ,P> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    P: FnMut(&I::Item, &J::Item) -> bool,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,P> fmt::Debug for JoinFilter<I,J,KI,KJ,P> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinFilter")
+            .field("join", &self.join)
+            .field("pred", &"<pred_fn>")
+            .finish()
+    }
+}
+
+
+/// Inner join combining the items of each row with a closure, created by `Joinable::join_map`.
+pub struct JoinMap<I, J, KI, KJ, F> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) join: JoinIt<I, J, KI, KJ>,
+    pub(crate) f: F,
+}
+
+
+impl<I,J,KI,KJ,K,F,R> Iterator for JoinMap<I,J,KI,KJ,F> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> R,
+    K: Ord
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (v, w) = self.join.next()?;
+
+        Some((self.f)(v, w))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ,K,F,R> DoubleEndedIterator for JoinMap<I,J,KI,KJ,F> where
+    I: DoubleEndedIterator,
+    J: DoubleEndedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> R,
+    K: Ord
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (v, w) = self.join.next_back()?;
+
+        Some((self.f)(v, w))
+    }
+}
+
+
+impl<I,J,KI,KJ,F> Clone for JoinMap<I,J,KI,KJ,F> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    F: Clone
+{
+    fn clone(&self) -> Self {
+        JoinMap {
+            join: self.join.clone(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,F,R> FusedIterator for JoinMap<I,J,KI,KJ,F> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> R,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,F> fmt::Debug for JoinMap<I,J,KI,KJ,F> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinMap")
+            .field("join", &self.join)
+            .field("f", &"<map_fn>")
+            .finish()
+    }
+}
+
+
+/// Inner join yielding the key of each row along with its items, created by
+/// `Joinable::join_with_key`.
+///
+/// The key is the one extracted from the left item when comparing it against the right side, so
+/// it is cloned for every row sharing it rather than extracted once more.
+pub struct JoinWithKey<I, J, KI, KJ, K> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) key: Option<K>,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinWithKey<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Clone
+{
+    type Item = (K, I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj, key) = (&mut self.ki, &mut self.kj, &mut self.key);
+
+        let row = self.merge.next_by(|v, w| {
+            let k = ki(v.clone());
+            let ordering = Ord::cmp(&k, &kj(w.clone()));
+            if ordering == Ordering::Equal {
+                *key = Some(k);
+            }
+            ordering
+        });
+        match row {
+            Some(Row::Both(v, w)) => self.key.clone().map(|k| (k, v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Clone for JoinWithKey<I,J,KI,KJ,K> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        JoinWithKey {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinWithKey<I,J,KI,KJ,K> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Clone
+{}
+
+
+impl<I,J,KI,KJ,K> fmt::Debug for JoinWithKey<I,J,KI,KJ,K> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinWithKey")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+
+/// Inner join yielding its rows in batches, created by `Joinable::join_chunks`.
+///
+/// Every batch holds `size` rows, except for the last one that holds the rows left over.
+pub struct JoinChunks<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) join: JoinIt<I, J, KI, KJ>,
+    pub(crate) size: usize,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinChunks<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = Vec<(I::Item, J::Item)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.join.by_ref().take(self.size).collect::<Vec<_>>();
+
+        if chunk.is_empty() { None } else { Some(chunk) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.join.size_hint();
+        // Rounding up without `div_ceil`, which would raise the minimum Rust version to 1.73.
+        let chunks = |n: usize| n / self.size + (n % self.size != 0) as usize;
+
+        (chunks(lo), hi.map(chunks))
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinChunks<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinChunks {
+            join: self.join.clone(),
+            size: self.size,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinChunks<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinChunks<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinChunks")
+            .field("join", &self.join)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+
+/// Inner join normalizing the extracted keys before comparing them, created by
+/// `JoinIt::map_left_key` and `JoinIt::map_right_key`.
+///
+/// Both sides have to be sorted by their transformed keys.
+pub struct KeyTransformed<I, J, KI, KJ, FI, FJ> where
+    I: Iterator,
+    J: Iterator
+{
+    merge: Merge<I, J>,
+    ki: KI,
+    kj: KJ,
+    fi: FI,
+    fj: FJ,
+}
+
+
+impl<I,J,KI,KJ,KA,KB> JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    KI: FnMut(I::Item) -> KA,
+    KJ: FnMut(J::Item) -> KB
+{
+    /// Transforms the keys of the left side with `f` after extracting them, leaving the key
+    /// extractor itself as it is.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![("Ann",1),("bo",2)];
+    /// let w = vec![("ann",'x'),("bo",'y')];
+    ///
+    /// let rows = v.join(w, |(k,_)| k.to_string(), |(k,_)| k.to_string())
+    ///     .map_left_key(|k| k.to_lowercase())
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'x'),(2,'y')], rows);
+    /// ```
+    pub fn map_left_key<F,K>(self, f: F) -> KeyTransformed<I,J,KI,KJ,F,Identity<KB>> where
+        F: FnMut(KA) -> K
+    {
+        KeyTransformed {
+            merge: self.merge,
+            ki: self.ki,
+            kj: self.kj,
+            fi: f,
+            fj: identity as fn(_) -> _,
+        }
+    }
+
+    /// Transforms the keys of the right side with `f` after extracting them, like `map_left_key`.
+    pub fn map_right_key<F,K>(self, f: F) -> KeyTransformed<I,J,KI,KJ,Identity<KA>,F> where
+        F: FnMut(KB) -> K
+    {
+        KeyTransformed {
+            merge: self.merge,
+            ki: self.ki,
+            kj: self.kj,
+            fi: identity as fn(_) -> _,
+            fj: f,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,FJ,KA> KeyTransformed<I,J,KI,KJ,Identity<KA>,FJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Transforms the keys of the left side too, like `JoinIt::map_left_key`.
+    pub fn map_left_key<F,K>(self, f: F) -> KeyTransformed<I,J,KI,KJ,F,FJ> where
+        F: FnMut(KA) -> K
+    {
+        KeyTransformed {
+            merge: self.merge,
+            ki: self.ki,
+            kj: self.kj,
+            fi: f,
+            fj: self.fj,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,FI,KB> KeyTransformed<I,J,KI,KJ,FI,Identity<KB>> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Transforms the keys of the right side too, like `JoinIt::map_right_key`.
+    pub fn map_right_key<F,K>(self, f: F) -> KeyTransformed<I,J,KI,KJ,FI,F> where
+        F: FnMut(KB) -> K
+    {
+        KeyTransformed {
+            merge: self.merge,
+            ki: self.ki,
+            kj: self.kj,
+            fi: self.fi,
+            fj: f,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,FI,FJ,KA,KB,K> Iterator for KeyTransformed<I,J,KI,KJ,FI,FJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> KA,
+    KJ: FnMut(J::Item) -> KB,
+    FI: FnMut(KA) -> K,
+    FJ: FnMut(KB) -> K,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj, fi, fj) = (&mut self.ki, &mut self.kj, &mut self.fi, &mut self.fj);
+        let mut left = |v| fi(ki(v));
+        let mut right = |w| fj(kj(w));
+
+        match self.merge.next_with(&mut Keys(&mut left, &mut right)) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => self.merge.next_in_back_group(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ,FI,FJ,KA,KB,K> DoubleEndedIterator for KeyTransformed<I,J,KI,KJ,FI,FJ> where
+    I: DoubleEndedIterator,
+    J: DoubleEndedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> KA,
+    KJ: FnMut(J::Item) -> KB,
+    FI: FnMut(KA) -> K,
+    FJ: FnMut(KB) -> K,
+    K: Ord
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (ki, kj, fi, fj) = (&mut self.ki, &mut self.kj, &mut self.fi, &mut self.fj);
+        let mut left = |v| fi(ki(v));
+        let mut right = |w| fj(kj(w));
+
+        self.merge.next_back_with(&mut Keys(&mut left, &mut right))
+    }
+}
+
+
+impl<I,J,KI,KJ,FI,FJ> Clone for KeyTransformed<I,J,KI,KJ,FI,FJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    FI: Clone,
+    FJ: Clone
+{
+    fn clone(&self) -> Self {
+        KeyTransformed {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            fi: self.fi.clone(),
+            fj: self.fj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,FI,FJ,KA,KB,K> FusedIterator for KeyTransformed<I,J,KI,KJ,FI,FJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> KA,
+    KJ: FnMut(J::Item) -> KB,
+    FI: FnMut(KA) -> K,
+    FJ: FnMut(KB) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,FI,FJ> fmt::Debug for KeyTransformed<I,J,KI,KJ,FI,FJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyTransformed")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("fi", &"<map_fn>")
+            .field("fj", &"<map_fn>")
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn join_filter() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d'), (3,'e')];
+        let w = [(1,10), (1,11), (2,20), (3,30)];
+
+        let join_it = v.iter().join_filter(w.iter(), |&(x,_)| x, |&(x,_)| x, |&&(_,a), &&(_,b)| a != 'c' && b != 20)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('b',10), ('b',11), ('e',30)], join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    fn join_filter_rejecting_everything() {
+        let v = [(0,'a'), (1,'b')];
+        let w = [(0,0), (1,10)];
+
+        let join_it = v.iter().join_filter(w.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| false);
+        assert_eq!( 0, join_it.size_hint().0 );
+        assert_eq!( 0, join_it.count() );
+    }
+
+    #[test]
+    fn join_filter_secondary_condition() {
+        let bids = vec![(1,10), (1,30), (2,5), (3,50)];
+        let asks = vec![(1,20), (1,25), (2,8), (3,40), (3,60)];
+
+        let rows = bids.join_filter(asks, |(k,_)| k, |(k,_)| k, |&(_,a), &(_,b)| a > b)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![((1,30),(1,20)), ((1,30),(1,25)), ((3,50),(3,40))], rows );
+    }
+
+    #[test]
+    fn join_with_key() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d'), (4,'e')];
+        let w = [(1,10), (1,11), (2,20), (3,30), (4,40)];
+
+        let rows = v.iter().join_with_key(w.iter(), |&(x,_)| x, |&(x,_)| x).collect::<Vec<_>>();
+        for &(k, &(kv,_), &(kw,_)) in &rows {
+            assert_eq!( k, kv );
+            assert_eq!( k, kw );
+        }
+        assert_eq!( vec![1, 1, 1, 1, 2, 4], rows.iter().map(|&(k,_,_)| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_with_key_non_copy_keys() {
+        let v = ["apple", "avocado", "cherry"];
+        let w = ["a", "b", "c"];
+
+        let rows = v.iter().join_with_key(w.iter(), |s| s[..1].to_string(), |s| s.to_string())
+            .map(|(k, &a, &b)| (k, a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("a".to_string(), "apple", "a"), ("a".to_string(), "avocado", "a"), ("c".to_string(), "cherry", "c")], rows );
+    }
+
+    #[test]
+    fn join_map_into_strings() {
+        let v = vec![(0,"zero"), (1,"one"), (2,"two")];
+        let w = vec![(1,'b'), (1,'c'), (2,'d')];
+
+        let rows = v.into_iter().join_map(w, |(x,_)| x, |(x,_)| x, |(_,a), (_,b)| format!("{}-{}", a, b))
+            .collect::<Vec<String>>();
+
+        assert_eq!( vec!["one-b".to_string(), "one-c".to_string(), "two-d".to_string()], rows );
+    }
+
+    #[test]
+    fn join_map_and_filter_reversed() {
+        let v = [(0,1), (1,2), (1,3), (2,4)];
+        let w = [(1,10), (1,20), (2,30)];
+
+        let sums = v.iter().join_map(w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), &(_,b)| a + b)
+            .rev()
+            .collect::<Vec<_>>();
+        assert_eq!( vec![34, 23, 13, 22, 12], sums );
+
+        let mut rows = v.iter().join_filter(w.iter(), |&(x,_)| x, |&(x,_)| x, |&&(_,a), _| a != 3);
+        assert_eq!( Some((&(2,4), &(2,30))), rows.next_back() );
+        assert_eq!( Some((&(1,2), &(1,10))), rows.next() );
+        assert_eq!( vec![(&(1,2), &(1,20))], rows.rev().collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn map_keys_of_both_sides() {
+        let v = vec![("Apple",1), ("banana",2), ("Cherry",3)];
+        let w = vec![("APPLE",'a'), ("CHERRY",'c'), ("DATE",'d')];
+
+        let rows = v.join(w, |(k,_)| k, |(k,_)| k)
+            .map_left_key(|k| k.to_lowercase())
+            .map_right_key(|k| k.to_lowercase())
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(1,'a'), (3,'c')], rows );
+    }
+
+    #[test]
+    fn map_right_key_next_back_meets_next() {
+        let v = vec![0, 1, 2];
+        let w = vec![0, 5, 10, 10];
+
+        let mut join_it = v.join(w, |k| k, |k| k).map_right_key(|k| k / 5);
+        assert_eq!( Some((0, 0)), join_it.next() );
+        assert_eq!( Some((2, 10)), join_it.next_back() );
+        assert_eq!( Some((1, 5)), join_it.next() );
+        assert_eq!( Some((2, 10)), join_it.next_back() );
+        assert_eq!( None, join_it.next() );
+        assert_eq!( None, join_it.next_back() );
+    }
+
+    #[test]
+    fn join_chunks_emits_the_partial_chunk() {
+        let v = (0..10).collect::<Vec<_>>();
+        let w = (0..12).collect::<Vec<_>>();
+
+        let chunks = v.join_chunks(w, |k| k, |k| k, 4)
+            .map(|chunk| chunk.len())
+            .collect::<Vec<_>>();
+        assert_eq!( vec![4, 4, 2], chunks );
+    }
+
+    #[test]
+    fn join_chunks_without_an_empty_last_chunk() {
+        let chunks = vec![1, 2, 2, 3].join_chunks(vec![2, 3], |k| k, |k| k, 3).collect::<Vec<_>>();
+        assert_eq!( vec![vec![(2, 2), (2, 2), (3, 3)]], chunks );
+
+        let chunks = vec![1, 2].join_chunks(vec![3, 4], |k| k, |k| k, 2);
+        assert_eq!( 0, chunks.count() );
+    }
+}
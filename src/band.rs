@@ -0,0 +1,567 @@
+//! Joins matching keys that are close to each other rather than equal.
+
+use alloc::collections::VecDeque;
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+use core::ops::Sub;
+
+
+/// The distance between two keys, never going below zero for unsigned keys.
+fn distance<K,D>(a: &K, b: &K) -> D where
+    K: Ord + Sub<Output = D> + Clone
+{
+    if a >= b {
+        a.clone() - b.clone()
+    } else {
+        b.clone() - a.clone()
+    }
+}
+
+
+/// Band join between two sorted iterators, created by `Joinable::join_within`.
+///
+/// Pairs every left item with each right item whose key is within the tolerance of its key. The
+/// right items in the band of the current left key are buffered along with their keys, and the
+/// band slides forward as the left keys grow. The tolerance is of the type of the distance between
+/// two keys, `K` itself unless the keys are something like `Instant`s.
+pub struct JoinWithin<I, J, KI, KJ, K, D = K> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: I,
+    pub(crate) j: Peekable<J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) tolerance: D,
+    pub(crate) curr: Option<(K, I::Item)>,
+    pub(crate) window: VecDeque<(K, J::Item)>,
+    pub(crate) pos: usize,
+}
+
+
+impl<I,J,KI,KJ,K,D> JoinWithin<I,J,KI,KJ,K,D> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{
+    /// Takes the next left item along with its key, sliding the window to the band around it.
+    pub(crate) fn next_left(&mut self) -> Option<(K, I::Item)> {
+        let v = self.i.next()?;
+        let k = (self.ki)(v.clone());
+        self.slide(&k);
+        Some((k, v))
+    }
+
+    /// Slides the window of right items to the band around `k`.
+    fn slide(&mut self, k: &K) {
+        while let Some((kw, _)) = self.window.front() {
+            if kw >= k || distance(k, kw) <= self.tolerance {
+                break;
+            }
+            self.window.pop_front();
+        }
+
+        let kj = &mut self.kj;
+        while let Some(kw) = self.j.peek().map(|w| kj(w.clone())) {
+            let near = distance(k, &kw) <= self.tolerance;
+            if !near && kw > *k {
+                break;
+            }
+            let w = self.j.next();
+            if near {
+                self.window.extend(w.map(|w| (kw, w)));
+            }
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> Iterator for JoinWithin<I,J,KI,KJ,K,D> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.curr.is_none() {
+                self.curr = Some(self.next_left()?);
+                self.pos = 0;
+            }
+
+            if let Some((_, w)) = self.window.get(self.pos) {
+                self.pos += 1;
+                return self.curr.as_ref().map(|(_, v)| (v.clone(), w.clone()));
+            }
+            self.curr = None;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, hi_i) = self.i.size_hint();
+        let hi_i = hi_i.and_then(|hi| hi.checked_add(self.curr.is_some() as usize));
+        let (_, hi_j) = self.j.size_hint();
+        let hi_j = hi_j.and_then(|hi| hi.checked_add(self.window.len()));
+
+        match (hi_i, hi_j) {
+            (Some(0), _) | (_, Some(0)) => (0, Some(0)),
+            (Some(i), Some(j)) => (0, i.checked_mul(j)),
+            _ => (0, None),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> Clone for JoinWithin<I,J,KI,KJ,K,D> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    K: Clone,
+    D: Clone
+{
+    fn clone(&self) -> Self {
+        JoinWithin {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            tolerance: self.tolerance.clone(),
+            curr: self.curr.clone(),
+            window: self.window.clone(),
+            pos: self.pos,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> FusedIterator for JoinWithin<I,J,KI,KJ,K,D> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{}
+
+
+impl<I,J,KI,KJ,K,D> fmt::Debug for JoinWithin<I,J,KI,KJ,K,D> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug,
+    K: fmt::Debug,
+    D: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinWithin")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("tolerance", &self.tolerance)
+            .field("curr", &self.curr)
+            .field("window", &self.window)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+
+/// As-of join between two sorted iterators, created by `Joinable::asof_join`.
+///
+/// Pairs every left item with the last right item whose key is at or before its key, or `None`
+/// when there is no such item. The right items are read ahead up to the current left key, holding
+/// on to the last one seen, so several left items can share the same right item.
+pub struct JoinAsOf<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: I,
+    pub(crate) j: Peekable<J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) last: Option<J::Item>,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinAsOf<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, Option<J::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.i.next()?;
+        let k = (self.ki)(v.clone());
+
+        let kj = &mut self.kj;
+        while self.j.peek().map_or(false, |w| kj(w.clone()) <= k) {
+            self.last = self.j.next();
+        }
+        Some((v, self.last.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.i.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinAsOf<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinAsOf {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            last: self.last.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinAsOf<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinAsOf<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinAsOf")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+
+/// Which of the right items within the window of a left item `TemporalJoin` pairs it with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowJoinStrategy {
+    /// The right item with the closest key, the earliest one of those at the same distance.
+    Nearest,
+    /// Every right item within the window, like `Joinable::join_within`.
+    AllWithinWindow,
+    /// The right item with the earliest key within the window.
+    FirstWithinWindow,
+}
+
+
+/// Temporal join between two sorted iterators, created by `temporal_join`.
+///
+/// A `JoinWithin` pairing every left item with the right items whose key is at most the window
+/// away from its key, as picked by the `WindowJoinStrategy`. Left items without any right item in
+/// their window are left out. The keys are typically timestamps, like `Instant` with a `Duration`
+/// window or nanoseconds in an `u64`.
+pub struct TemporalJoin<I, J, KI, KJ, K, D> where
+    I: Iterator,
+    J: Iterator
+{
+    join: JoinWithin<I, J, KI, KJ, K, D>,
+    strategy: WindowJoinStrategy,
+}
+
+
+/// Joins the sorted iterators `i` and `j`, pairing every item of `i` with all the items of `j`
+/// whose key is at most `window` away.
+///
+/// Call `with_strategy` on the join before advancing it to pick the nearest or the first item
+/// within the window instead.
+///
+/// ```
+/// use join_it::{temporal_join, WindowJoinStrategy};
+///
+/// let clicks = vec![(100u64,'a'), (250,'b'), (400,'c')];
+/// let views = vec![(90u64,"x"), (105,"y"), (260,"z")];
+///
+/// let rows = temporal_join(clicks.clone(), views.clone(), |(t,_)| t, |(t,_)| t, 20)
+///     .map(|((_,c),(_,v))| (c, v))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![('a',"x"), ('a',"y"), ('b',"z")], rows);
+///
+/// let rows = temporal_join(clicks, views, |(t,_)| t, |(t,_)| t, 20)
+///     .with_strategy(WindowJoinStrategy::Nearest)
+///     .map(|((_,c),(_,v))| (c, v))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![('a',"y"), ('b',"z")], rows);
+/// ```
+pub fn temporal_join<I,J,KI,KJ,K,D>(i: I, j: J, ki: KI, kj: KJ, window: D) -> TemporalJoin<I::IntoIter,J::IntoIter,KI,KJ,K,D> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{
+    let join = JoinWithin {
+        i: i.into_iter(),
+        j: j.into_iter().peekable(),
+        ki,
+        kj,
+        tolerance: window,
+        curr: None,
+        window: VecDeque::new(),
+        pos: 0,
+    };
+
+    TemporalJoin { join, strategy: WindowJoinStrategy::AllWithinWindow }
+}
+
+
+impl<I,J,KI,KJ,K,D> TemporalJoin<I,J,KI,KJ,K,D> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Sets which right items within the window are paired, `AllWithinWindow` by default.
+    pub fn with_strategy(mut self, strategy: WindowJoinStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> Iterator for TemporalJoin<I,J,KI,KJ,K,D> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.strategy == WindowJoinStrategy::AllWithinWindow {
+            return self.join.next();
+        }
+
+        loop {
+            let (k, v) = self.join.next_left()?;
+            let window = &self.join.window;
+
+            let nearest = match self.strategy {
+                WindowJoinStrategy::Nearest => window.iter()
+                    .min_by(|(ka, _), (kb, _)| Ord::cmp(&distance(&k, ka), &distance(&k, kb))),
+                _ => window.front(),
+            };
+            if let Some((_, w)) = nearest {
+                return Some((v, w.clone()));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.strategy {
+            WindowJoinStrategy::AllWithinWindow => self.join.size_hint(),
+            _ => (0, self.join.i.size_hint().1),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> Clone for TemporalJoin<I,J,KI,KJ,K,D> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    K: Clone,
+    D: Clone
+{
+    fn clone(&self) -> Self {
+        TemporalJoin {
+            join: self.join.clone(),
+            strategy: self.strategy,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> FusedIterator for TemporalJoin<I,J,KI,KJ,K,D> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Sub<Output = D> + Clone,
+    D: Ord
+{}
+
+
+impl<I,J,KI,KJ,K,D> fmt::Debug for TemporalJoin<I,J,KI,KJ,K,D> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug,
+    K: fmt::Debug,
+    D: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TemporalJoin")
+            .field("join", &self.join)
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn join_within_spans_two_neighbours() {
+        let v = vec![(10,'a'), (20,'b'), (35,'c')];
+        let w = vec![(9,"x"), (11,"y"), (14,"z"), (40,"w")];
+
+        let rows = v.join_within(w, |(k,_)| k, |(k,_)| k, 1)
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('a',"x"), ('a',"y")], rows );
+    }
+
+    #[test]
+    fn join_within_overlapping_bands() {
+        let v = vec![10u32, 12, 13, 30];
+        let w = vec![8u32, 11, 14, 15, 29, 31];
+
+        let rows = v.join_within(w, |k| k, |k| k, 2).collect::<Vec<_>>();
+        assert_eq!( vec![(10,8), (10,11), (12,11), (12,14), (13,11), (13,14), (13,15), (30,29), (30,31)], rows );
+    }
+
+    #[test]
+    fn join_within_zero_tolerance_is_an_inner_join() {
+        let v = vec![1, 2, 2, 4, 5];
+        let w = vec![0, 2, 2, 3, 5];
+
+        let inner = v.clone().join(w.clone(), |k| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( inner, v.join_within(w, |k| k, |k| k, 0).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_within_duplicate_keys_on_both_sides() {
+        let v = vec![5, 5, 9];
+        let w = vec![0, 4, 4, 6];
+
+        let rows = v.join_within(w, |k| k, |k| k, 1).collect::<Vec<_>>();
+        assert_eq!( vec![(5, 4), (5, 4), (5, 6), (5, 4), (5, 4), (5, 6)], rows );
+
+        let join_it = vec![1, 2].join_within(Vec::<i32>::new(), |k| k, |k| k, 5);
+        assert_eq!( 0, join_it.count() );
+    }
+
+    #[test]
+    fn asof_join_shares_the_preceding_row() {
+        let trades = vec![(1,'a'), (5,'b'), (6,'c'), (7,'d'), (10,'e'), (12,'f')];
+        let quotes = vec![(3,"q3"), (10,"q10")];
+
+        let rows = trades.asof_join(quotes, |(k,_)| k, |(k,_)| k)
+            .map(|((_,t),q)| (t, q.map(|(_,q)| q)))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('a',None), ('b',Some("q3")), ('c',Some("q3")), ('d',Some("q3")), ('e',Some("q10")), ('f',Some("q10"))], rows );
+    }
+
+    #[test]
+    fn asof_join_takes_the_last_of_equal_keys() {
+        let v = vec![2, 4];
+        let w = vec![(1,'x'), (2,'y'), (2,'z'), (5,'w')];
+
+        let rows = v.asof_join(w, |k| k, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![(2, Some((2,'z'))), (4, Some((2,'z')))], rows );
+    }
+
+    #[test]
+    fn asof_join_empty_right_side() {
+        let join_it = vec![1, 2].asof_join(Vec::<i32>::new(), |k| k, |k| k);
+        assert_eq!( (2, Some(2)), join_it.size_hint() );
+        assert_eq!( vec![(1, None), (2, None)], join_it.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn temporal_join_strategies() {
+        let v = vec![10u64, 20, 50, 70];
+        let w = vec![4u64, 9, 13, 22, 69, 71];
+        let join = temporal_join(v, w, |k| k, |k| k, 6u64);
+
+        assert_eq!( vec![(10,4), (10,9), (10,13), (20,22), (70,69), (70,71)], join.clone().collect::<Vec<_>>() );
+        assert_eq!( vec![(10,4), (20,22), (70,69)], join.clone().with_strategy(WindowJoinStrategy::FirstWithinWindow).collect::<Vec<_>>() );
+        assert_eq!( vec![(10,9), (20,22), (70,69)], join.with_strategy(WindowJoinStrategy::Nearest).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn temporal_join_instants() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let v = vec![(at(100),'a'), (at(200),'b')];
+        let w = vec![(at(95),"x"), (at(180),"y"), (at(203),"z")];
+
+        let rows = temporal_join(v, w, |(t,_)| t, |(t,_)| t, Duration::from_millis(10))
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('a',"x"), ('b',"z")], rows );
+    }
+
+    #[test]
+    fn temporal_join_nearest_ties_and_empty_windows() {
+        let v = vec![1u32, 10, 20];
+        let w = vec![8u32, 12, 30];
+
+        let rows = temporal_join(v, w, |k| k, |k| k, 2)
+            .with_strategy(WindowJoinStrategy::Nearest)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(10, 8)], rows );
+
+        let join_it = temporal_join(vec![1u32, 2], Vec::<u32>::new(), |k| k, |k| k, 5)
+            .with_strategy(WindowJoinStrategy::FirstWithinWindow);
+        assert_eq!( 0, join_it.count() );
+    }
+}
@@ -0,0 +1,150 @@
+//! A builder picking the kind of join to run over two sorted sequences.
+
+use core::fmt;
+use merge::{Merge, Order};
+use {AntiJoinIt, FullJoinIt, JoinIt, LeftJoinIt, RightJoinIt, SemiJoinIt};
+
+
+/// The two sides of a join and their key extractors, created by `Joinable::join_builder`.
+///
+/// Options like the sort order are set on the builder first, then one of `inner`, `left`,
+/// `right`, `full`, `semi` or `anti` turns it into the iterator of that kind of join.
+pub struct JoinBuilder<I, J, KI, KJ> {
+    pub(crate) i: I,
+    pub(crate) j: J,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) order: Order,
+}
+
+
+impl<I,J,KI,KJ> JoinBuilder<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Sets the direction in which both sides are sorted, `Order::Ascending` by default.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Inner join, like `Joinable::join`.
+    pub fn inner(self) -> JoinIt<I,J,KI,KJ> {
+        JoinIt {
+            merge: Merge::new(self.i, self.j, false, false).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+
+    /// Left outer join, like `Joinable::left_join`.
+    pub fn left(self) -> LeftJoinIt<I,J,KI,KJ> {
+        LeftJoinIt {
+            merge: Merge::new(self.i, self.j, true, false).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+
+    /// Right outer join, like `Joinable::right_join`.
+    pub fn right(self) -> RightJoinIt<I,J,KI,KJ> {
+        RightJoinIt {
+            merge: Merge::new(self.i, self.j, false, true).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+
+    /// Full outer join, like `Joinable::full_join`.
+    pub fn full(self) -> FullJoinIt<I,J,KI,KJ> {
+        FullJoinIt {
+            merge: Merge::new(self.i, self.j, true, true).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+
+    /// Semi join, like `Joinable::semi_join`.
+    pub fn semi(self) -> SemiJoinIt<I,J,KI,KJ> {
+        SemiJoinIt {
+            merge: Merge::new(self.i, self.j, false, false).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+
+    /// Anti join, like `Joinable::anti_join`.
+    pub fn anti(self) -> AntiJoinIt<I,J,KI,KJ> {
+        AntiJoinIt {
+            merge: Merge::new(self.i, self.j, true, false).with_order(self.order),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinBuilder<I,J,KI,KJ> where
+    I: Clone,
+    J: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinBuilder {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            order: self.order,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinBuilder<I,J,KI,KJ> where
+    I: fmt::Debug,
+    J: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinBuilder")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn join_builder_kinds_match_the_shortcuts() {
+        let v = vec![0, 1, 1, 3];
+        let w = vec![1, 2, 3, 3];
+        let builder = v.clone().join_builder(w.clone(), |k| k, |k| k);
+
+        assert_eq!( v.clone().join(w.clone(), |k| k, |k| k).collect::<Vec<_>>(), builder.clone().inner().collect::<Vec<_>>() );
+        assert_eq!( v.clone().left_join(w.clone(), |k| k, |k| k).collect::<Vec<_>>(), builder.clone().left().collect::<Vec<_>>() );
+        assert_eq!( v.clone().right_join(w.clone(), |k| k, |k| k).collect::<Vec<_>>(), builder.clone().right().collect::<Vec<_>>() );
+        assert_eq!( v.clone().full_join(w.clone(), |k| k, |k| k).collect::<Vec<_>>(), builder.clone().full().collect::<Vec<_>>() );
+        assert_eq!( v.clone().semi_join(w.clone(), |k| k, |k| k).collect::<Vec<_>>(), builder.clone().semi().collect::<Vec<_>>() );
+        assert_eq!( v.anti_join(w, |k| k, |k| k).collect::<Vec<_>>(), builder.anti().collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_builder_descending() {
+        let v = vec![3, 2, 1, 0];
+        let w = vec![3, 3, 1];
+        let builder = v.join_builder(w, |k| k, |k| k).with_order(Order::Descending);
+
+        assert_eq!( vec![(3, 3), (3, 3), (1, 1)], builder.clone().inner().collect::<Vec<_>>() );
+        assert_eq!( vec![(3, Some(3)), (3, Some(3)), (2, None), (1, Some(1)), (0, None)], builder.clone().left().collect::<Vec<_>>() );
+        assert_eq!( vec![3, 1], builder.clone().semi().collect::<Vec<_>>() );
+        assert_eq!( vec![2, 0], builder.anti().collect::<Vec<_>>() );
+    }
+}
@@ -0,0 +1,402 @@
+//! Joins ordered by a comparator rather than by the `Ord` of extracted keys.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Compare, KeysBy, Merge, Row};
+use Joinable;
+
+
+/// Inner join between two iterators sorted by a comparator, created by `Joinable::join_by`.
+pub struct JoinBy<I, J, F> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) cmp: F,
+}
+
+
+/// Maps f over the join between `i` and `j`, matching the items for which `cmp` returns `Equal`.
+///
+/// ```
+/// use join_it::join_it_by;
+/// let v = vec![(0.5f64,'a'),(1.5,'b')];
+/// let w = vec![(1.5f64,11)];
+/// join_it_by(v, w, |a, b| a.0.total_cmp(&b.0), |(_,a),(_,b)| {
+///     println!("Join result: ({},{})", a, b);
+/// });
+/// ```
+pub fn join_it_by<I,J,C,F>( i: I, j: J, cmp: C, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    C: Fn(&I::Item, &J::Item) -> Ordering,
+    F: FnMut(I::Item, J::Item),
+{
+    for (v, w) in i.join_by(j, cmp) {
+        f(v, w);
+    }
+}
+
+
+impl<I,J,F> Iterator for JoinBy<I,J,F> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    F: FnMut(&I::Item, &J::Item) -> Ordering
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.merge.next_by(&mut self.cmp) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,F> Clone for JoinBy<I,J,F> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    F: Clone
+{
+    fn clone(&self) -> Self {
+        JoinBy {
+            merge: self.merge.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+
+impl<I,J,F> FusedIterator for JoinBy<I,J,F> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    F: FnMut(&I::Item, &J::Item) -> Ordering
+{}
+
+
+impl<I,J,F> fmt::Debug for JoinBy<I,J,F> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinBy")
+            .field("merge", &self.merge)
+            .field("cmp", &"<cmp_fn>")
+            .finish()
+    }
+}
+
+
+/// Inner join between two iterators sorted by several columns, created by
+/// `Joinable::join_by_keys`.
+pub struct JoinByKeys<'a, I, J, F: 'a> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) keys: &'a [F],
+}
+
+
+/// Compares items column by column, until a column tells them apart.
+struct Columns<'a, F: 'a>(&'a [F]);
+
+
+impl<'a,A,B,F> Compare<A,B> for Columns<'a,F> where
+    F: Fn(&A, &B) -> Ordering
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        self.0.iter()
+            .map(|key| key(v, w))
+            .find(|&ordering| ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+
+impl<'a,I,J,F> Iterator for JoinByKeys<'a,I,J,F> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    F: Fn(&I::Item, &J::Item) -> Ordering
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.merge.next_with(&mut Columns(self.keys)) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<'a,I,J,F> Clone for JoinByKeys<'a,I,J,F> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone
+{
+    fn clone(&self) -> Self {
+        JoinByKeys {
+            merge: self.merge.clone(),
+            keys: self.keys,
+        }
+    }
+}
+
+
+impl<'a,I,J,F> FusedIterator for JoinByKeys<'a,I,J,F> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    F: Fn(&I::Item, &J::Item) -> Ordering
+{}
+
+
+impl<'a,I,J,F> fmt::Debug for JoinByKeys<'a,I,J,F> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinByKeys")
+            .field("merge", &self.merge)
+            .field("keys", &self.keys.len())
+            .finish()
+    }
+}
+
+
+/// Inner join between two iterators sorted by a comparator of their keys, created by
+/// `join_with_cmp`.
+pub struct JoinItCmp<I, J, KI, KJ, CMP> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) cmp: CMP,
+}
+
+
+/// Joins `i` and `j` like `Joinable::join`, ordering the keys extracted by `ki` and `kj` with
+/// `cmp` rather than with `Ord`. Both sides have to be sorted by `cmp`.
+///
+/// ```
+/// use join_it::join_with_cmp;
+///
+/// let v = vec![(3,'a'),(2,'b'),(1,'c')];
+/// let w = vec![(3,33),(1,11)];
+///
+/// let rows = join_with_cmp(v, w, |(k,_)| k, |(k,_)| k, |a: &i32, b: &i32| b.cmp(a))
+///     .map(|((_,a),(_,b))| (a, b))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![('a',33),('c',11)], rows);
+/// ```
+pub fn join_with_cmp<I,J,K,KI,KJ,CMP>(i: I, j: J, ki: KI, kj: KJ, cmp: CMP) -> JoinItCmp<I::IntoIter,J::IntoIter,KI,KJ,CMP> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    CMP: FnMut(&K, &K) -> Ordering
+{
+    JoinItCmp {
+        merge: Merge::new(i.into_iter(), j.into_iter(), false, false),
+        ki,
+        kj,
+        cmp,
+    }
+}
+
+
+impl<I,J,KI,KJ,K,CMP> Iterator for JoinItCmp<I,J,KI,KJ,CMP> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    CMP: FnMut(&K, &K) -> Ordering
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj, cmp) = (&mut self.ki, &mut self.kj, &mut self.cmp);
+
+        match self.merge.next_with(&mut KeysBy(ki, kj, cmp)) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ,CMP> Clone for JoinItCmp<I,J,KI,KJ,CMP> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    CMP: Clone
+{
+    fn clone(&self) -> Self {
+        JoinItCmp {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,CMP> FusedIterator for JoinItCmp<I,J,KI,KJ,CMP> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    CMP: FnMut(&K, &K) -> Ordering
+{}
+
+
+impl<I,J,KI,KJ,CMP> fmt::Debug for JoinItCmp<I,J,KI,KJ,CMP> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinItCmp")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("cmp", &"<cmp_fn>")
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn join_by_partial_ord_keys() {
+        let v = [(0.5f64,'a'), (1.0,'b'), (2.5,'c')];
+        let w = [(1.0f64,10), (2.0,20), (2.5,25)];
+
+        let join_it = v.iter().join_by(w.iter(), |a, b| a.0.total_cmp(&b.0))
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('b',10), ('c',25)], join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    fn join_by_case_insensitive() {
+        let v = vec!["Apple", "banana", "Cherry"];
+        let w = vec!["apple", "CHERRY", "date"];
+
+        let mut r = vec![];
+        join_it_by(v, w, |a, b| a.to_lowercase().cmp(&b.to_lowercase()), |a, b| r.push((a, b)));
+
+        assert_eq!( vec![("Apple","apple"), ("Cherry","CHERRY")], r );
+    }
+
+    #[test]
+    fn join_with_cmp_descending() {
+        let v = vec![(5,'a'), (3,'b'), (3,'c'), (1,'d')];
+        let w = vec![(6,60), (3,30), (3,31), (1,10), (0,0)];
+
+        let rows = join_with_cmp(v, w, |(k,_)| k, |(k,_)| k, |a: &u32, b: &u32| b.cmp(a))
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('b',30), ('b',31), ('c',30), ('c',31), ('d',10)], rows );
+    }
+
+    #[test]
+    fn join_with_cmp_keys_without_ord() {
+        let v = vec![0.5f64, 1.0, 1.0, f64::NAN];
+        let w = vec![1.0f64, 1.0, 3.0, f64::NAN];
+
+        let rows = join_with_cmp(v, w, |k| k, |k| k, f64::total_cmp).collect::<Vec<_>>();
+        assert_eq!( 5, rows.len() );
+        assert_eq!( vec![(1.0, 1.0); 4], rows[..4].to_vec() );
+        assert!( rows[4].0.is_nan() && rows[4].1.is_nan() );
+    }
+
+    #[test]
+    fn join_by_keys_two_columns() {
+        let v = vec![(1,'a',"x"), (1,'b',"y"), (2,'a',"z")];
+        let w = vec![(1,'b',10), (2,'a',20), (2,'b',30)];
+
+        type Key = fn(&(u32,char,&str), &(u32,char,u32)) -> Ordering;
+        let keys: [Key; 2] = [|a, b| a.0.cmp(&b.0), |a, b| a.1.cmp(&b.1)];
+        let rows = v.join_by_keys(w, &keys)
+            .map(|(a, b)| (a.2, b.2))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("y",10), ("z",20)], rows );
+    }
+
+    #[test]
+    fn join_by_keys_three_columns() {
+        let sales = vec![("eu",2023,"pen"), ("eu",2024,"cup"), ("eu",2024,"pen"), ("us",2024,"pen")];
+        let prices = vec![("eu",2024,"pen",3), ("us",2023,"pen",2), ("us",2024,"pen",4)];
+
+        type Sale = (&'static str, u32, &'static str);
+        type Price = (&'static str, u32, &'static str, u32);
+        let keys: Vec<fn(&Sale, &Price) -> Ordering> = vec![
+            |a, b| a.0.cmp(b.0),
+            |a, b| a.1.cmp(&b.1),
+            |a, b| a.2.cmp(b.2),
+        ];
+        let rows = sales.join_by_keys(prices, &keys).collect::<Vec<_>>();
+        assert_eq!( vec![(("eu",2024,"pen"), ("eu",2024,"pen",3)), (("us",2024,"pen"), ("us",2024,"pen",4))], rows );
+    }
+
+    #[test]
+    fn join_by_keys_duplicate_rows() {
+        let v = vec![(1,'a',0), (1,'a',1), (1,'b',2)];
+        let w = vec![(1,'a',10), (1,'a',11), (2,'a',20)];
+
+        type Key = fn(&(u32,char,u32), &(u32,char,u32)) -> Ordering;
+        let keys: [Key; 2] = [|a, b| a.0.cmp(&b.0), |a, b| a.1.cmp(&b.1)];
+        let rows = v.join_by_keys(w, &keys)
+            .map(|(a, b)| (a.2, b.2))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(0,10), (0,11), (1,10), (1,11)], rows );
+    }
+}
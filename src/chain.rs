@@ -0,0 +1,213 @@
+//! Chains of joins, flattening the rows of each join into a single tuple.
+
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+use {JoinIt, Joinable};
+
+
+/// Appends an item to a tuple, giving the rows of a `JoinChain`.
+///
+/// Implemented for tuples of two up to seven items, so chains give rows of at most eight.
+pub trait Append<T> {
+    type Output;
+
+    fn append(self, item: T) -> Self::Output;
+}
+
+
+macro_rules! append {
+    ($($t:ident),+) => {
+        impl<$($t,)+ Z> Append<Z> for ($($t,)+) {
+            type Output = ($($t,)+ Z);
+
+            #[allow(non_snake_case)]
+            fn append(self, item: Z) -> Self::Output {
+                let ($($t,)+) = self;
+                ($($t,)+ item)
+            }
+        }
+    };
+}
+
+append!(A, B);
+append!(A, B, C);
+append!(A, B, C, D);
+append!(A, B, C, D, E);
+append!(A, B, C, D, E, F);
+append!(A, B, C, D, E, F, G);
+
+
+/// A join of the rows of an earlier join with another sorted sequence, created by
+/// `JoinIt::then_join`.
+///
+/// The item of the other sequence is appended to the row instead of nesting it in another tuple,
+/// so joining `a`, `b` and `c` gives rows of `(A, B, C)`. The key extractor of the left side takes
+/// the whole row. Every link of the chain is a separate type, so the length of a chain is fixed
+/// when it's written down.
+pub struct JoinChain<R, L, KR, KL> where
+    R: Iterator,
+    L: Iterator
+{
+    join: JoinIt<R, L, KR, KL>,
+}
+
+
+impl<I,J,KI,KJ,K> JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    /// Joins the rows of this join with `other`, sorted by the same key, appending the item of
+    /// `other` to each row.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let users = vec![(1,"ann"),(2,"bo"),(3,"cy")];
+    /// let orders = vec![(1,"book"),(3,"pen")];
+    /// let payments = vec![(1,10),(3,30)];
+    ///
+    /// let rows = users.join(orders, |(k,_)| k, |(k,_)| k)
+    ///     .then_join(payments, |((k,_),_)| k, |(k,_)| k)
+    ///     .map(|((_,u),(_,o),(_,p))| (u, o, p))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("ann","book",10),("cy","pen",30)], rows);
+    /// ```
+    pub fn then_join<L,KR,KL,K2>(self, other: L, left_key: KR, right_key: KL) -> JoinChain<Self,L::IntoIter,KR,KL> where
+        L: IntoIterator,
+        L::Item: Clone,
+        KR: FnMut((I::Item, J::Item)) -> K2,
+        KL: FnMut(L::Item) -> K2,
+        K2: Ord
+    {
+        JoinChain { join: self.join(other, left_key, right_key) }
+    }
+}
+
+
+impl<R,L,KR,KL,K> JoinChain<R,L,KR,KL> where
+    R: Iterator,
+    L: Iterator,
+    R::Item: Clone + Append<L::Item>,
+    L::Item: Clone,
+    KR: FnMut(R::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord
+{
+    /// Joins the rows of this chain with `other` like `JoinIt::then_join`, `left_key` taking the
+    /// flattened row.
+    pub fn then_join<M,KR2,KM,K2>(self, other: M, left_key: KR2, right_key: KM) -> JoinChain<Self,M::IntoIter,KR2,KM> where
+        M: IntoIterator,
+        M::Item: Clone,
+        <R::Item as Append<L::Item>>::Output: Clone,
+        KR2: FnMut(<R::Item as Append<L::Item>>::Output) -> K2,
+        KM: FnMut(M::Item) -> K2,
+        K2: Ord
+    {
+        JoinChain { join: self.join(other, left_key, right_key) }
+    }
+}
+
+
+impl<R,L,KR,KL,K> Iterator for JoinChain<R,L,KR,KL> where
+    R: Iterator,
+    L: Iterator,
+    R::Item: Clone + Append<L::Item>,
+    L::Item: Clone,
+    KR: FnMut(R::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord
+{
+    type Item = <R::Item as Append<L::Item>>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.join.next().map(|(row, w)| row.append(w))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<R,L,KR,KL> Clone for JoinChain<R,L,KR,KL> where
+    R: Iterator + Clone,
+    L: Iterator + Clone,
+    R::Item: Clone,
+    L::Item: Clone,
+    KR: Clone,
+    KL: Clone
+{
+    fn clone(&self) -> Self {
+        JoinChain { join: self.join.clone() }
+    }
+}
+
+
+impl<R,L,KR,KL,K> FusedIterator for JoinChain<R,L,KR,KL> where
+    R: FusedIterator,
+    L: FusedIterator,
+    R::Item: Clone + Append<L::Item>,
+    L::Item: Clone,
+    KR: FnMut(R::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord
+{}
+
+
+impl<R,L,KR,KL> fmt::Debug for JoinChain<R,L,KR,KL> where
+    R: Iterator + fmt::Debug,
+    L: Iterator + fmt::Debug,
+    R::Item: fmt::Debug,
+    L::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinChain")
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn then_join_flattens_rows() {
+        let a = vec![(1,'a'), (2,'b'), (3,'c')];
+        let b = vec![(1,"one"), (3,"three")];
+        let c = vec![(0,0.0), (1,1.5), (3,3.5)];
+
+        let rows = a.join(b, |(k,_)| k, |(k,_)| k)
+            .then_join(c, |((k,_),_)| k, |(k,_)| k)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![((1,'a'), (1,"one"), (1,1.5)), ((3,'c'), (3,"three"), (3,3.5))], rows );
+    }
+
+    #[test]
+    fn then_join_four_sequences() {
+        let rows = vec![1, 2, 3, 4].join(vec![2, 3, 4], |k| k, |k| k)
+            .then_join(vec![3, 4, 5], |(k,_)| k, |k| k)
+            .then_join(vec![4, 4], |(k,_,_)| k, |k| k)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(4, 4, 4, 4), (4, 4, 4, 4)], rows );
+    }
+
+    #[test]
+    fn then_join_empty_sides() {
+        let rows = vec![1, 2].join(vec![3, 4], |k| k, |k| k)
+            .then_join(vec![1, 2, 3], |(k,_)| k, |k| k);
+        assert_eq!( 0, rows.count() );
+
+        let rows = vec![1, 2].join(vec![1, 2], |k| k, |k| k)
+            .then_join(Vec::<u32>::new(), |(k,_)| k, |k| k);
+        assert_eq!( (0, Some(0)), rows.size_hint() );
+        assert_eq!( 0, rows.count() );
+    }
+}
@@ -0,0 +1,215 @@
+//! Joins of maps, and collecting the rows of a join into maps keyed by the join key.
+
+use alloc::collections::{btree_map, BTreeMap};
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+use {JoinIt, Joinable};
+
+
+impl<I,J,KI,KJ,K> JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    /// Collects the rows into a `BTreeMap` by their key, extracted once more from the left item.
+    ///
+    /// Each key holds a single row, so when duplicate keys give several rows the last one of them
+    /// overwrites the others.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'a'),(2,'b')];
+    /// let w = vec![(1,11),(2,22),(3,33)];
+    ///
+    /// let map = v.join(w, |(k,_)| k, |(k,_)| k).collect_into_btreemap();
+    /// assert_eq!(Some(&((2,'b'),(2,22))), map.get(&2));
+    /// assert_eq!(2, map.len());
+    /// ```
+    pub fn collect_into_btreemap(mut self) -> BTreeMap<K, (I::Item, J::Item)> {
+        let mut map = BTreeMap::new();
+
+        while let Some(row) = self.next() {
+            map.insert((self.ki)(row.0.clone()), row);
+        }
+        map
+    }
+
+    /// Collects the rows into a `HashMap` by their key, like `collect_into_btreemap`, the last of
+    /// the rows sharing a key staying in the map.
+    #[cfg(feature = "std")]
+    pub fn collect_into_map(mut self) -> HashMap<K, (I::Item, J::Item)> where
+        K: Hash
+    {
+        let mut map = HashMap::new();
+
+        while let Some(row) = self.next() {
+            map.insert((self.ki)(row.0.clone()), row);
+        }
+        map
+    }
+}
+
+
+
+/// Extracts the key of an entry of a `BTreeMap`.
+type EntryKey<'a, K, V> = fn((&'a K, &'a V)) -> &'a K;
+
+/// Joins the entries of two `BTreeMap`s by their keys.
+type EntryJoin<'a, K, A, B> = JoinIt<btree_map::Iter<'a, K, A>, btree_map::Iter<'a, K, B>, EntryKey<'a, K, A>, EntryKey<'a, K, B>>;
+
+
+/// Inner join of two `BTreeMap`s on their keys, created by `join_maps`.
+pub struct JoinMaps<'a, K: 'a, A: 'a, B: 'a> {
+    join: EntryJoin<'a, K, A, B>,
+}
+
+
+/// Joins the maps `a` and `b` on their keys, yielding the key along with the value of each map
+/// for every key found in both.
+///
+/// The maps iterate in key order, so no key extractors are needed, and every key is found once in
+/// each map, so every key gives a single row.
+///
+/// ```
+/// use join_it::join_maps;
+/// use std::collections::BTreeMap;
+///
+/// let a = vec![(1,'a'),(2,'b')].into_iter().collect::<BTreeMap<_,_>>();
+/// let b = vec![(2,22),(3,33)].into_iter().collect::<BTreeMap<_,_>>();
+///
+/// assert_eq!(vec![(&2, &'b', &22)], join_maps(&a, &b).collect::<Vec<_>>());
+/// ```
+pub fn join_maps<'a,K,A,B>(a: &'a BTreeMap<K,A>, b: &'a BTreeMap<K,B>) -> JoinMaps<'a,K,A,B> where
+    K: Ord
+{
+    fn key<'a,K,V>((k, _): (&'a K, &'a V)) -> &'a K {
+        k
+    }
+
+    JoinMaps { join: a.iter().join(b.iter(), key as EntryKey<K, A>, key as EntryKey<K, B>) }
+}
+
+
+impl<'a,K,A,B> Iterator for JoinMaps<'a,K,A,B> where
+    K: Ord
+{
+    type Item = (&'a K, &'a A, &'a B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.join.next().map(|((k, a), (_, b))| (k, a, b))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<'a,K,A,B> DoubleEndedIterator for JoinMaps<'a,K,A,B> where
+    K: Ord
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.join.next_back().map(|((k, a), (_, b))| (k, a, b))
+    }
+}
+
+
+impl<'a,K,A,B> Clone for JoinMaps<'a,K,A,B> {
+    fn clone(&self) -> Self {
+        JoinMaps { join: self.join.clone() }
+    }
+}
+
+
+impl<'a,K,A,B> FusedIterator for JoinMaps<'a,K,A,B> where
+    K: Ord
+{}
+
+
+impl<'a,K,A,B> fmt::Debug for JoinMaps<'a,K,A,B> where
+    K: fmt::Debug,
+    A: fmt::Debug,
+    B: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinMaps")
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn collect_into_map_by_key() {
+        let v = vec![(0,'a'), (1,'b'), (3,'c')];
+        let w = vec![(1,"one"), (2,"two"), (3,"three")];
+
+        let map = v.join(w, |(k,_)| k, |(k,_)| k).collect_into_map();
+        assert_eq!( 2, map.len() );
+        assert_eq!( Some(&((1,'b'), (1,"one"))), map.get(&1) );
+        assert_eq!( Some(&((3,'c'), (3,"three"))), map.get(&3) );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn collect_into_maps_last_row_wins() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c')];
+        let w = vec![(1,10), (1,11), (2,20)];
+
+        let map = v.clone().join(w.clone(), |(k,_)| k, |(k,_)| k).collect_into_map();
+        assert_eq!( Some(&((1,'b'), (1,11))), map.get(&1) );
+
+        let map = v.join(w, |(k,_)| k, |(k,_)| k).collect_into_btreemap();
+        assert_eq!( vec![(1, ((1,'b'), (1,11))), (2, ((2,'c'), (2,20)))], map.into_iter().collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_maps_of_strings() {
+        let mut a = BTreeMap::new();
+        let mut b = BTreeMap::new();
+        for (k, name) in [(1u32, "ann"), (2, "bo"), (4, "cy")] {
+            a.insert(k, name.to_string());
+        }
+        for (k, city) in [(0u32, "oslo"), (2, "rome"), (4, "lima"), (5, "kyiv")] {
+            b.insert(k, city.to_string());
+        }
+
+        let rows = join_maps(&a, &b)
+            .map(|(k, name, city)| (*k, name.as_str(), city.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(2, "bo", "rome"), (4, "cy", "lima")], rows );
+        assert_eq!( Some((&4, &a[&4], &b[&4])), join_maps(&a, &b).next_back() );
+    }
+
+    #[test]
+    fn join_maps_next_back_meets_next() {
+        let a = (0..4u32).map(|k| (k, k * 10)).collect::<BTreeMap<_,_>>();
+        let b = (1..4u32).map(|k| (k, k * 100)).collect::<BTreeMap<_,_>>();
+
+        let mut rows = join_maps(&a, &b);
+        assert_eq!( Some((&1, &10, &100)), rows.next() );
+        assert_eq!( Some((&3, &30, &300)), rows.next_back() );
+        assert_eq!( Some((&2, &20, &200)), rows.next_back() );
+        assert_eq!( None, rows.next() );
+        assert_eq!( None, rows.next_back() );
+
+        let empty = BTreeMap::<u32, u32>::new();
+        assert_eq!( 0, join_maps(&a, &empty).count() );
+    }
+}
@@ -0,0 +1,139 @@
+//! Deduplication of sorted sequences by key.
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+
+/// Iterator adaptor yielding the first item of each run of items sharing a key, created by
+/// `dedup_by_key`.
+///
+/// Only consecutive items are compared, so a sorted sequence comes out with every key once, as the
+/// inner join of two sequences with unique keys is one-to-one.
+pub struct DedupByKey<I, KF, K> {
+    iter: I,
+    key: KF,
+    prev: Option<K>,
+}
+
+
+/// Collapses the runs of items of the sorted `iter` whose keys, as extracted by `key`, are equal,
+/// keeping the first item of each run.
+///
+/// ```
+/// use join_it::dedup_by_key;
+///
+/// let v = vec![(1,'a'),(1,'b'),(2,'c'),(3,'d'),(3,'e')];
+///
+/// let rows = dedup_by_key(v, |(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![(1,'a'),(2,'c'),(3,'d')], rows);
+/// ```
+pub fn dedup_by_key<I,KF,K>(iter: I, key: KF) -> DedupByKey<I::IntoIter,KF,K> where
+    I: IntoIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{
+    DedupByKey {
+        iter: iter.into_iter(),
+        key,
+        prev: None,
+    }
+}
+
+
+impl<I,KF,K> Iterator for DedupByKey<I,KF,K> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let v = self.iter.next()?;
+            let k = (self.key)(v.clone());
+
+            if self.prev.as_ref() != Some(&k) {
+                self.prev = Some(k);
+                return Some(v);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        let lo = if self.prev.is_none() { lo.min(1) } else { 0 };
+
+        (lo, hi)
+    }
+}
+
+
+impl<I,KF,K> Clone for DedupByKey<I,KF,K> where
+    I: Clone,
+    KF: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        DedupByKey {
+            iter: self.iter.clone(),
+            key: self.key.clone(),
+            prev: self.prev.clone(),
+        }
+    }
+}
+
+
+impl<I,KF,K> FusedIterator for DedupByKey<I,KF,K> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{}
+
+
+impl<I,KF,K> fmt::Debug for DedupByKey<I,KF,K> where
+    I: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DedupByKey")
+            .field("iter", &self.iter)
+            .field("key", &"<key_fn>")
+            .field("prev", &self.prev)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn dedup_by_key_keeps_the_first_of_each_run() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c'), (2,'d'), (2,'e'), (3,'f')];
+
+        let rows = dedup_by_key(v, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![(1,'a'), (2,'c'), (3,'f')], rows );
+    }
+
+    #[test]
+    fn dedup_by_key_last_run_at_the_end() {
+        let mut dedup = dedup_by_key(vec![1, 2, 2, 2], |k| k);
+        assert_eq!( Some(1), dedup.next() );
+        assert_eq!( vec![2], dedup.clone().collect::<Vec<_>>() );
+        assert_eq!( Some(2), dedup.next() );
+        assert_eq!( None, dedup.next() );
+    }
+
+    #[test]
+    fn dedup_by_key_before_a_join() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c')];
+        let w = vec![1, 1, 2];
+
+        let rows = dedup_by_key(v, |(k,_)| k).join(w, |(k,_)| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( vec![((1,'a'),1), ((1,'a'),1), ((2,'c'),2)], rows );
+    }
+}
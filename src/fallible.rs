@@ -0,0 +1,487 @@
+//! Joins of iterators over fallible items or with fallible key extractors, stopping at the first
+//! error.
+
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Merge, Row};
+
+
+/// The items of a fallible iterator, split into the value joined on and the error ending the join.
+///
+/// Implemented for `Result`, naming its value and error types in the bounds of `TryJoinIt`.
+pub trait Fallible {
+    type Value;
+    type Error;
+
+    fn into_result(self) -> Result<Self::Value, Self::Error>;
+}
+
+
+impl<T,E> Fallible for Result<T, E> {
+    type Value = T;
+    type Error = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+
+/// Maps the items of a `Stash` to the values it yields, or to the error it stashes.
+pub(crate) trait TryMap<A> {
+    type Value;
+    type Error;
+
+    fn try_map(&mut self, a: A) -> Result<Self::Value, Self::Error>;
+}
+
+
+/// Maps `Fallible` items to their result.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct IntoResult;
+
+
+impl<A> TryMap<A> for IntoResult where
+    A: Fallible
+{
+    type Value = A::Value;
+    type Error = A::Error;
+
+    fn try_map(&mut self, a: A) -> Result<A::Value, A::Error> {
+        a.into_result()
+    }
+}
+
+
+/// Pairs items with their key, the error of the key extractor ending the `Stash`.
+#[derive(Clone)]
+pub(crate) struct WithKey<KF>(pub(crate) KF);
+
+
+impl<A,KF,K,E> TryMap<A> for WithKey<KF> where
+    A: Clone,
+    KF: FnMut(A) -> Result<K, E>
+{
+    type Value = (K, A);
+    type Error = E;
+
+    fn try_map(&mut self, a: A) -> Result<(K, A), E> {
+        (self.0)(a.clone()).map(|k| (k, a))
+    }
+}
+
+
+impl<KF> fmt::Debug for WithKey<KF> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("WithKey")
+            .field(&"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Yields the items of an iterator mapped by `F`, stashing the first error and ending there.
+pub(crate) struct Stash<I, F> where
+    I: Iterator,
+    F: TryMap<I::Item>
+{
+    iter: I,
+    map: F,
+    pub(crate) err: Option<F::Error>,
+    failed: bool,
+}
+
+
+impl<I,F> Stash<I,F> where
+    I: Iterator,
+    F: TryMap<I::Item>
+{
+    pub(crate) fn new(iter: I, map: F) -> Self {
+        Stash { iter, map, err: None, failed: false }
+    }
+}
+
+
+impl<I,F> Clone for Stash<I,F> where
+    I: Iterator + Clone,
+    F: TryMap<I::Item> + Clone,
+    F::Error: Clone
+{
+    fn clone(&self) -> Self {
+        Stash {
+            iter: self.iter.clone(),
+            map: self.map.clone(),
+            err: self.err.clone(),
+            failed: self.failed,
+        }
+    }
+}
+
+
+impl<I,F> fmt::Debug for Stash<I,F> where
+    I: Iterator + fmt::Debug,
+    F: TryMap<I::Item> + fmt::Debug,
+    F::Error: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Stash")
+            .field("iter", &self.iter)
+            .field("map", &self.map)
+            .field("err", &self.err)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+
+impl<I,F> Iterator for Stash<I,F> where
+    I: Iterator,
+    F: TryMap<I::Item>
+{
+    type Item = F::Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.map.try_map(self.iter.next()?) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.err = Some(e);
+                self.failed = true;
+                None
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            (0, Some(0))
+        } else {
+            (0, self.iter.size_hint().1)
+        }
+    }
+}
+
+
+/// Inner join between two sorted iterators of `Result`s, created by `TryJoinIt::new`.
+///
+/// The keys are extracted from the `Ok` values. The first `Err` of either side is returned as soon
+/// as the join needs to look past it, after which the iterator is exhausted. Rows paired up before
+/// that are returned as usual.
+pub struct TryJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Fallible,
+    J::Item: Fallible
+{
+    merge: Merge<Stash<I, IntoResult>, Stash<J, IntoResult>>,
+    ki: KI,
+    kj: KJ,
+}
+
+
+impl<I,J,KI,KJ,T,U,E> TryJoinIt<I,J,KI,KJ> where
+    I: Iterator<Item = Result<T, E>>,
+    J: Iterator<Item = Result<U, E>>
+{
+    /// Joins `i` and `j`, comparing the keys extracted by `ki` and `kj` from their `Ok` values.
+    pub fn new<II,JJ,K>(i: II, j: JJ, ki: KI, kj: KJ) -> Self where
+        II: IntoIterator<IntoIter = I, Item = Result<T, E>>,
+        JJ: IntoIterator<IntoIter = J, Item = Result<U, E>>,
+        KI: FnMut(&T) -> K,
+        KJ: FnMut(&U) -> K
+    {
+        TryJoinIt {
+            merge: Merge::new(Stash::new(i.into_iter(), IntoResult), Stash::new(j.into_iter(), IntoResult), false, false),
+            ki,
+            kj,
+        }
+    }
+}
+
+
+/// Maps f over the join between the fallible iterators `i` and `j`, based on the key extractors
+/// `ki` and `kj` taking the `Ok` values by reference, and returns the first error of either side.
+///
+/// ```
+/// use join_it::try_join_it;
+/// let v: Vec<Result<(u32,char),String>> = vec![Ok((0,'a')), Ok((1,'b')), Err("broken".to_string())];
+/// let w: Vec<Result<(u32,u32),String>> = vec![Ok((1,11)), Ok((2,22))];
+/// let mut rows = vec![];
+/// let res = try_join_it(v, w, |&(k,_)| k, |&(k,_)| k, |(_,a), (_,b)| rows.push((a, b)));
+/// assert_eq!(Err("broken".to_string()), res);
+/// assert_eq!(vec![('b',11)], rows);
+/// ```
+pub fn try_join_it<I,J,K,KI,KJ,F,T,U,E>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) -> Result<(), E> where
+    I: IntoIterator<Item = Result<T, E>>,
+    J: IntoIterator<Item = Result<U, E>>,
+    T: Clone,
+    U: Clone,
+    KI: Fn(&T) -> K,
+    KJ: Fn(&U) -> K,
+    F: FnMut(T, U),
+    K: Ord
+{
+    for row in TryJoinIt::new(i, j, ki, kj) {
+        let (v, w) = row?;
+        f(v, w);
+    }
+    Ok(())
+}
+
+
+impl<I,J,KI,KJ,K,T,U,E> Iterator for TryJoinIt<I,J,KI,KJ> where
+    I: Iterator<Item = Result<T, E>>,
+    J: Iterator<Item = Result<U, E>>,
+    T: Clone,
+    U: Clone,
+    KI: FnMut(&T) -> K,
+    KJ: FnMut(&U) -> K,
+    K: Ord
+{
+    type Item = Result<(T, U), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        if let Some(Row::Both(v, w)) = self.merge.next_by(|v, w| Ord::cmp(&ki(v), &kj(w))) {
+            return Some(Ok((v, w)));
+        }
+        // Only the first error is reported, an error of the other side is dropped along with it.
+        let (i, j) = self.merge.iters_mut();
+        match (i.err.take(), j.err.take()) {
+            (Some(e), _) | (_, Some(e)) => Some(Err(e)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, hi) = self.merge.size_hint();
+        (0, hi.and_then(|hi| hi.checked_add(1)))
+    }
+}
+
+
+impl<I,J,KI,KJ,T,U,E> Clone for TryJoinIt<I,J,KI,KJ> where
+    I: Iterator<Item = Result<T, E>> + Clone,
+    J: Iterator<Item = Result<U, E>> + Clone,
+    T: Clone,
+    U: Clone,
+    E: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        TryJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,T,U,E> FusedIterator for TryJoinIt<I,J,KI,KJ> where
+    I: FusedIterator<Item = Result<T, E>>,
+    J: FusedIterator<Item = Result<U, E>>,
+    T: Clone,
+    U: Clone,
+    KI: FnMut(&T) -> K,
+    KJ: FnMut(&U) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,T,U,E> fmt::Debug for TryJoinIt<I,J,KI,KJ> where
+    I: Iterator<Item = Result<T, E>> + fmt::Debug,
+    J: Iterator<Item = Result<U, E>> + fmt::Debug,
+    T: fmt::Debug,
+    U: fmt::Debug,
+    E: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TryJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Inner join with fallible key extractors, created by `Joinable::try_join_by_key`.
+///
+/// Every key is extracted once, as its item is reached. The first error of either extractor is
+/// returned as soon as the join needs to look past the item it failed on, after which the iterator
+/// is exhausted.
+pub struct TryJoinByKey<I, J, KI, KJ, K, E> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> Result<K, E>,
+    KJ: FnMut(J::Item) -> Result<K, E>
+{
+    pub(crate) merge: Merge<Stash<I, WithKey<KI>>, Stash<J, WithKey<KJ>>>,
+}
+
+
+impl<I,J,KI,KJ,K,E> TryJoinByKey<I,J,KI,KJ,K,E> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> Result<K, E>,
+    KJ: FnMut(J::Item) -> Result<K, E>
+{
+    pub(crate) fn new(i: I, j: J, ki: KI, kj: KJ) -> Self {
+        TryJoinByKey { merge: Merge::new(Stash::new(i, WithKey(ki)), Stash::new(j, WithKey(kj)), false, false) }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,E> Iterator for TryJoinByKey<I,J,KI,KJ,K,E> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> Result<K, E>,
+    KJ: FnMut(J::Item) -> Result<K, E>,
+    K: Ord + Clone
+{
+    type Item = Result<(I::Item, J::Item), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(Row::Both((_, v), (_, w))) = self.merge.next_by(|v, w| Ord::cmp(&v.0, &w.0)) {
+            return Some(Ok((v, w)));
+        }
+        let (i, j) = self.merge.iters_mut();
+        match (i.err.take(), j.err.take()) {
+            (Some(e), _) | (_, Some(e)) => Some(Err(e)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, hi) = self.merge.size_hint();
+        (0, hi.and_then(|hi| hi.checked_add(1)))
+    }
+}
+
+
+impl<I,J,KI,KJ,K,E> Clone for TryJoinByKey<I,J,KI,KJ,K,E> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> Result<K, E> + Clone,
+    KJ: FnMut(J::Item) -> Result<K, E> + Clone,
+    K: Clone,
+    E: Clone
+{
+    fn clone(&self) -> Self {
+        TryJoinByKey { merge: self.merge.clone() }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,E> FusedIterator for TryJoinByKey<I,J,KI,KJ,K,E> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> Result<K, E>,
+    KJ: FnMut(J::Item) -> Result<K, E>,
+    K: Ord + Clone
+{}
+
+
+impl<I,J,KI,KJ,K,E> fmt::Debug for TryJoinByKey<I,J,KI,KJ,K,E> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: Clone + fmt::Debug,
+    J::Item: Clone + fmt::Debug,
+    KI: FnMut(I::Item) -> Result<K, E>,
+    KJ: FnMut(J::Item) -> Result<K, E>,
+    K: fmt::Debug,
+    E: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TryJoinByKey")
+            .field("merge", &self.merge)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn try_join_without_errors() {
+        let v: Vec<Result<(u32,char),()>> = vec![Ok((0,'a')), Ok((1,'b')), Ok((1,'c')), Ok((2,'d'))];
+        let w: Vec<Result<(u32,u32),()>> = vec![Ok((1,11)), Ok((2,22)), Ok((3,33))];
+
+        let rows = TryJoinIt::new(v, w, |&(x,_)| x, |&(x,_)| x)
+            .map(|row| row.map(|((_,a),(_,b))| (a, b)))
+            .collect::<Result<Vec<_>,()>>();
+        assert_eq!( Ok(vec![('b',11), ('c',11), ('d',22)]), rows );
+    }
+
+    #[test]
+    fn try_join_left_error() {
+        let v: Vec<Result<(u32,char),&str>> = vec![Ok((1,'b')), Err("left"), Ok((2,'c'))];
+        let w: Vec<Result<(u32,u32),&str>> = vec![Ok((1,11)), Ok((2,22))];
+
+        let mut join_it = TryJoinIt::new(v, w, |&(x,_)| x, |&(x,_)| x);
+        assert_eq!( Some(Ok(((1,'b'),(1,11)))), join_it.next() );
+        assert_eq!( Some(Err("left")), join_it.next() );
+        assert_eq!( None, join_it.next() );
+    }
+
+    #[test]
+    fn try_join_right_error() {
+        let v: Vec<Result<(u32,char),&str>> = vec![Ok((1,'b')), Ok((1,'c')), Ok((3,'d'))];
+        let w: Vec<Result<(u32,u32),&str>> = vec![Ok((1,11)), Err("right"), Err("again")];
+
+        let mut rows = vec![];
+        let res = try_join_it(v, w, |&(x,_)| x, |&(x,_)| x, |(_,a), (_,b)| rows.push((a, b)));
+        assert_eq!( Err("right"), res );
+        assert_eq!( vec![('b',11), ('c',11)], rows );
+    }
+
+    #[test]
+    fn try_join_errors_on_both_sides() {
+        let v: Vec<Result<(u32,char),&str>> = vec![Err("left")];
+        let w: Vec<Result<(u32,u32),&str>> = vec![Err("right")];
+
+        let join_it = TryJoinIt::new(v, w, |&(x,_)| x, |&(x,_)| x);
+        assert_eq!( vec![Err("left")], join_it.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn try_join_by_key_failing_parse() {
+        let v = [("1",'a'), ("2",'b'), ("x",'c'), ("4",'d')];
+        let w = [("1",10), ("2",20), ("3",30), ("4",40)];
+
+        let mut join_it = v.iter().try_join_by_key(w.iter(), |&(k,_)| k.parse::<u32>(), |&(k,_)| k.parse::<u32>())
+            .map(|row| row.map(|(&(_,a),&(_,b))| (a, b)));
+        assert_eq!( Some(Ok(('a',10))), join_it.next() );
+        assert_eq!( Some(Ok(('b',20))), join_it.next() );
+        assert!( join_it.next().unwrap().is_err() );
+        assert_eq!( None, join_it.next() );
+    }
+
+    #[test]
+    fn try_join_by_key_without_errors() {
+        let v = vec!["1", "2", "2", "5"];
+        let w = vec!["2", "3", "5"];
+
+        let rows = v.into_iter().try_join_by_key(w, |k| k.parse::<u8>(), |k| k.parse::<u8>())
+            .collect::<Result<Vec<_>,_>>();
+        assert_eq!( Ok(vec![("2","2"), ("2","2"), ("5","5")]), rows );
+    }
+}
@@ -0,0 +1,221 @@
+//! Hash join of unsorted sequences.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+
+/// The side of a `HashJoin` collected into a table, along with the other side looked up in it.
+enum BuildSide<I, J, K> where
+    I: Iterator,
+    J: Iterator
+{
+    Left(HashMap<K, Vec<I::Item>>, J),
+    Right(I, HashMap<K, Vec<J::Item>>),
+}
+
+
+/// Hash join between two iterators in any order, created by `hash_join`.
+///
+/// The side with the lower bound of `size_hint` that's the smallest is collected into a table of
+/// its items by key right away, and the other side is looked up in it item by item. The rows
+/// come in the order of the side looked up, the items sharing a key in the table in their
+/// original order.
+pub struct HashJoin<I, J, KI, KJ, K> where
+    I: Iterator,
+    J: Iterator
+{
+    side: BuildSide<I, J, K>,
+    ki: KI,
+    kj: KJ,
+    rows: VecDeque<(I::Item, J::Item)>,
+}
+
+
+/// Joins `i` and `j`, which don't have to be sorted, based on the key extractors `ki` and `kj`
+/// hashing the keys.
+///
+/// ```
+/// use join_it::hash_join;
+///
+/// let v = vec![(2,'c'),(0,'a'),(1,'b')];
+/// let w = vec![(1,11),(3,33),(2,22)];
+///
+/// let rows = hash_join(v, w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![((1,'b'),(1,11)), ((2,'c'),(2,22))], rows);
+/// ```
+pub fn hash_join<I,J,K,KI,KJ>( i: I, j: J, mut ki: KI, mut kj: KJ ) -> HashJoin<I::IntoIter,J::IntoIter,KI,KJ,K> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Hash + Eq
+{
+    let (i, j) = (i.into_iter(), j.into_iter());
+
+    let side = if i.size_hint().0 <= j.size_hint().0 {
+        let mut table = HashMap::new();
+        for v in i {
+            table.entry(ki(v.clone())).or_insert_with(Vec::new).push(v);
+        }
+        BuildSide::Left(table, j)
+    } else {
+        let mut table = HashMap::new();
+        for w in j {
+            table.entry(kj(w.clone())).or_insert_with(Vec::new).push(w);
+        }
+        BuildSide::Right(i, table)
+    };
+
+    HashJoin { side, ki, kj, rows: VecDeque::new() }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for HashJoin<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Hash + Eq
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(row);
+            }
+
+            match self.side {
+                BuildSide::Left(ref table, ref mut j) => {
+                    let w = j.next()?;
+                    if let Some(vs) = table.get(&(self.kj)(w.clone())) {
+                        self.rows.extend(vs.iter().map(|v| (v.clone(), w.clone())));
+                    }
+                },
+                BuildSide::Right(ref mut i, ref table) => {
+                    let v = i.next()?;
+                    if let Some(ws) = table.get(&(self.ki)(v.clone())) {
+                        self.rows.extend(ws.iter().map(|w| (v.clone(), w.clone())));
+                    }
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let empty = match self.side {
+            BuildSide::Left(ref table, _) => table.is_empty(),
+            BuildSide::Right(_, ref table) => table.is_empty(),
+        };
+
+        (self.rows.len(), if empty { Some(self.rows.len()) } else { None })
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Clone for HashJoin<I,J,KI,KJ,K> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        let side = match self.side {
+            BuildSide::Left(ref table, ref j) => BuildSide::Left(table.clone(), j.clone()),
+            BuildSide::Right(ref i, ref table) => BuildSide::Right(i.clone(), table.clone()),
+        };
+
+        HashJoin {
+            side,
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for HashJoin<I,J,KI,KJ,K> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Hash + Eq
+{}
+
+
+impl<I,J,KI,KJ,K> fmt::Debug for HashJoin<I,J,KI,KJ,K> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut f = f.debug_struct("HashJoin");
+        match self.side {
+            BuildSide::Left(ref table, ref j) => f.field("table", table).field("j", j),
+            BuildSide::Right(ref i, ref table) => f.field("i", i).field("table", table),
+        };
+        f.field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("rows", &self.rows)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn sorted<T: Ord>(mut rows: Vec<T>) -> Vec<T> {
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn hash_join_matches_the_sorted_join() {
+        let v = vec![(3,'d'), (1,'a'), (2,'c'), (1,'b'), (5,'e')];
+        let w = vec![(2,20), (1,10), (4,40), (1,11), (5,50), (2,21)];
+
+        let mut sv = v.clone();
+        let mut sw = w.clone();
+        sv.sort();
+        sw.sort();
+        let expected = sorted(sv.join(sw, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>());
+
+        assert_eq!( expected, sorted(hash_join(v.clone(), w.clone(), |(k,_)| k, |(k,_)| k).collect()) );
+        assert_eq!( expected, sorted(hash_join(v.into_iter().filter(|_| true), w, |(k,_)| k, |(k,_)| k).collect()) );
+    }
+
+    #[test]
+    fn unsorted_join_keeps_the_order_looked_up() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c')];
+        let w = vec![(2,20), (0,0), (1,10), (2,21)];
+
+        let rows = v.unsorted_join(w, |(k,_)| k, |(k,_)| k)
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('c',20), ('a',10), ('b',10), ('c',21)], rows );
+    }
+
+    #[test]
+    fn hash_join_empty_sides() {
+        let empty: Vec<u32> = vec![];
+
+        assert_eq!( 0, hash_join(vec![2, 1], empty.clone(), |k| k, |k| k).count() );
+        assert_eq!( 0, hash_join(empty, vec![1, 1, 2], |k| k, |k| k).count() );
+    }
+}
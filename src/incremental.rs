@@ -0,0 +1,178 @@
+//! Joins driven by the right side, for a right sequence that keeps on growing.
+
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+
+
+/// Incremental join between two sorted iterators, created by `Joinable::incremental_join`.
+///
+/// Pairs every right item with the left item of its key, stepping through the right items one by
+/// one. The last matched left item and its key are cached, so consecutive right items with the
+/// same key are paired without touching the left side, which only moves once a right key gets past
+/// the cached one. The left keys are expected to be unique, later left items sharing a key are
+/// skipped.
+///
+/// Running out of right items doesn't lose the cache, so the join can be resumed when more are
+/// appended, for instance with a right side reading from a channel with `try_iter`.
+pub struct IncrementalJoinIt<I, J, KI, KJ, K> where
+    I: Iterator
+{
+    pub(crate) i: I,
+    pub(crate) j: J,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) cached_left: Option<I::Item>,
+    pub(crate) cached_key: Option<K>,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for IncrementalJoinIt<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let w = self.j.next()?;
+            let kw = (self.kj)(w.clone());
+
+            let ordering = self.cached_key.as_ref().map(|k| k.cmp(&kw));
+            if ordering == Some(Ordering::Greater) {
+                continue;
+            }
+            if ordering != Some(Ordering::Equal) {
+                self.cached_left = None;
+                self.cached_key = None;
+                for v in self.i.by_ref() {
+                    let k = (self.ki)(v.clone());
+                    if k >= kw {
+                        self.cached_left = Some(v);
+                        self.cached_key = Some(k);
+                        break;
+                    }
+                }
+                match self.cached_key {
+                    Some(ref k) if *k == kw => (),
+                    Some(_) => continue,
+                    None => return None,
+                }
+            }
+            return self.cached_left.clone().map(|v| (v, w));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.j.size_hint().1)
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Clone for IncrementalJoinIt<I,J,KI,KJ,K> where
+    I: Iterator + Clone,
+    J: Clone,
+    I::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        IncrementalJoinIt {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            cached_left: self.cached_left.clone(),
+            cached_key: self.cached_key.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for IncrementalJoinIt<I,J,KI,KJ,K> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,K> fmt::Debug for IncrementalJoinIt<I,J,KI,KJ,K> where
+    I: Iterator + fmt::Debug,
+    J: fmt::Debug,
+    I::Item: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IncrementalJoinIt")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("cached_left", &self.cached_left)
+            .field("cached_key", &self.cached_key)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn incremental_join_reuses_the_cached_left_item() {
+        let pulled = Cell::new(0);
+        let users = vec![(1,"ann"), (2,"bo"), (4,"cy")];
+        let events = vec![(1,'a'), (1,'b'), (1,'c'), (3,'d'), (4,'e'), (4,'f'), (5,'g')];
+
+        let rows = users.into_iter()
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .incremental_join(events, |(k,_)| k, |(k,_)| k)
+            .map(|((_,u),(_,e))| (u, e))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("ann",'a'), ("ann",'b'), ("ann",'c'), ("cy",'e'), ("cy",'f')], rows );
+        assert_eq!( 3, pulled.get() );
+    }
+
+    #[test]
+    fn incremental_join_resumes_after_the_right_side_runs_dry() {
+        use std::sync::mpsc::channel;
+
+        let (tx, rx) = channel();
+        let mut join_it = vec![1, 3, 5].incremental_join(rx.try_iter(), |k| k, |k| k);
+        assert_eq!( None, join_it.next() );
+
+        tx.send(1).unwrap();
+        tx.send(1).unwrap();
+        assert_eq!( vec![(1, 1), (1, 1)], join_it.by_ref().collect::<Vec<_>>() );
+
+        tx.send(2).unwrap();
+        tx.send(5).unwrap();
+        assert_eq!( vec![(5, 5)], join_it.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn incremental_join_ignores_right_items_after_the_left_side_ends() {
+        use std::sync::mpsc::channel;
+
+        let (tx, rx) = channel();
+        let mut join_it = vec![1].incremental_join(rx.try_iter(), |k| k, |k| k);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+        assert_eq!( vec![(1, 1)], join_it.by_ref().collect::<Vec<_>>() );
+
+        tx.send(1).unwrap();
+        assert_eq!( None, join_it.next() );
+    }
+}
@@ -0,0 +1,342 @@
+//! Keys of items: composite keys joining on several columns at once, items exposing their own
+//! key, and keys borrowed from the items.
+
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Compare, Merge, Row};
+use JoinIt;
+
+
+/// An item with a natural key to join on, used by `Joinable::join_by_key`.
+///
+/// References to items with a key have the same key, so sequences can be joined by reference.
+///
+/// With the `derive` feature, `#[derive(HasKey)]` implements it for a struct from the field marked
+/// `#[key]`, see the `join_it_derive` crate.
+pub trait HasKey<K> {
+    fn key(&self) -> K;
+}
+
+
+impl<K,T> HasKey<K> for &T where
+    T: HasKey<K> + ?Sized
+{
+    fn key(&self) -> K {
+        (**self).key()
+    }
+}
+
+
+/// The key extractor of `Joinable::join_by_key`.
+pub(crate) type KeyOf<T, K> = fn(T) -> K;
+
+
+pub(crate) fn key_of<T, K>(v: T) -> K where
+    T: HasKey<K>
+{
+    v.key()
+}
+
+
+/// Inner join of two sorted sequences by the keys of their items, created by
+/// `Joinable::join_by_key`.
+pub type JoinByKey<I, J, K> = JoinIt<I, J, KeyOf<<I as Iterator>::Item, K>, KeyOf<<J as Iterator>::Item, K>>;
+
+
+/// Builds a key extractor returning a tuple of keys, one for each of the given accessors.
+///
+/// `composite_key!(|x| x.dept, |x| x.emp_id)` works like `|x| (x.dept, x.emp_id)`, every accessor
+/// getting its own clone of the item. Tuples are ordered by their first key, then their second and
+/// so on, so the sequences have to be sorted the same way. A column sorted in descending order is
+/// wrapped in `std::cmp::Reverse`.
+///
+/// The closure needs the type of its item from the join it's passed to, so the macro is meant to be
+/// used right in the argument list of a join.
+///
+/// ```
+/// #[macro_use]
+/// extern crate join_it;
+///
+/// use join_it::Joinable;
+/// use std::cmp::Reverse;
+///
+/// # fn main() {
+/// let staff = vec![(1,30,"ann"),(1,20,"bo"),(2,10,"cy")];
+/// let desks = vec![(1,30,'a'),(2,10,'c')];
+///
+/// let rows = staff.join(desks, composite_key!(|x| x.0, |x| Reverse(x.1)), composite_key!(|x| x.0, |x| Reverse(x.1)))
+///     .map(|((_,_,name),(_,_,desk))| (name, desk))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![("ann",'a'),("cy",'c')], rows);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! composite_key {
+    ($(|$x:ident| $key:expr),+ $(,)*) => {
+        |item| ($({ let $x = item.clone(); $key },)+)
+    };
+}
+
+
+/// Inner join between two sorted iterators by keys borrowed from their items, created by
+/// `Joinable::join_ref`.
+///
+/// The key extractors take the items by reference and return a reference into them, so keys like
+/// `String`s are compared in place instead of being cloned along with their items for every
+/// comparison.
+pub struct JoinRef<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+/// Compares items by the keys borrowed from them.
+struct RefKeys<'a, KI: 'a, KJ: 'a>(&'a mut KI, &'a mut KJ);
+
+
+impl<'a,A,B,K,KI,KJ> Compare<A,B> for RefKeys<'a,KI,KJ> where
+    KI: FnMut(&A) -> &K,
+    KJ: FnMut(&B) -> &K,
+    K: Ord + ?Sized
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        Ord::cmp((self.0)(v), (self.1)(w))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, v0: &A, v1: &A) -> Option<Ordering> {
+        Some(Ord::cmp((self.0)(v0), (self.0)(v1)))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, w0: &B, w1: &B) -> Option<Ordering> {
+        Some(Ord::cmp((self.1)(w0), (self.1)(w1)))
+    }
+}
+
+
+/// Maps f over the join between `i` and `j` like `join_it`, with key extractors borrowing the
+/// keys from the items like `Joinable::join_ref`.
+///
+/// ```
+/// use join_it::join_it_ref;
+/// let v = vec!["ann".to_string(), "bo".to_string()];
+/// let w = vec!["bo".to_string(), "cy".to_string()];
+/// let mut rows = Vec::new();
+/// join_it_ref(&v, &w, |s| s.as_str(), |s| s.as_str(), |a, b| rows.push((a, b)));
+/// assert_eq!(vec![(&v[1], &w[0])], rows);
+/// ```
+pub fn join_it_ref<I,J,K,KI,KJ,F>( i: I, j: J, mut ki: KI, mut kj: KJ, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(&I::Item) -> &K,
+    KJ: FnMut(&J::Item) -> &K,
+    F: FnMut(I::Item, J::Item),
+    K: Ord + ?Sized
+{
+    let mut merge = Merge::new(i.into_iter(), j.into_iter(), false, false);
+
+    while let Some(Row::Both(v, w)) = merge.next_with(&mut RefKeys(&mut ki, &mut kj)) {
+        f(v, w);
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinRef<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(&I::Item) -> &K,
+    KJ: FnMut(&J::Item) -> &K,
+    K: Ord + ?Sized
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.merge.next_with(&mut RefKeys(&mut self.ki, &mut self.kj)) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinRef<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinRef {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinRef<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(&I::Item) -> &K,
+    KJ: FnMut(&J::Item) -> &K,
+    K: Ord + ?Sized
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinRef<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinRef")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::cmp::Reverse;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Employee {
+        dept: u32,
+        emp_id: u32,
+        name: &'static str,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Badge {
+        dept: u32,
+        emp_id: u32,
+        code: u32,
+    }
+
+    fn employee(dept: u32, emp_id: u32, name: &'static str) -> Employee {
+        Employee { dept, emp_id, name }
+    }
+
+    fn badge(dept: u32, emp_id: u32, code: u32) -> Badge {
+        Badge { dept, emp_id, code }
+    }
+
+    #[test]
+    fn composite_key_ascending_columns() {
+        let staff = [employee(1, 1, "ann"), employee(1, 2, "bo"), employee(2, 1, "cy"), employee(2, 3, "di")];
+        let badges = [badge(1, 2, 12), badge(2, 1, 21), badge(2, 2, 22), badge(2, 3, 23)];
+
+        let rows = staff.iter().join(badges.iter(), composite_key!(|e| e.dept, |e| e.emp_id), composite_key!(|b| b.dept, |b| b.emp_id))
+            .map(|(e, b)| (e.name, b.code))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("bo", 12), ("cy", 21), ("di", 23)], rows );
+    }
+
+    #[test]
+    fn composite_key_descending_column() {
+        let staff = [employee(1, 2, "bo"), employee(1, 1, "ann"), employee(2, 3, "di"), employee(2, 1, "cy")];
+        let badges = [badge(1, 2, 12), badge(2, 3, 23), badge(2, 2, 22), badge(2, 1, 21)];
+
+        let rows = staff.iter().join(badges.iter(), composite_key!(|e| e.dept, |e| Reverse(e.emp_id)), composite_key!(|b| b.dept, |b| Reverse(b.emp_id)))
+            .map(|(e, b)| (e.name, b.code))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("bo", 12), ("di", 23), ("cy", 21)], rows );
+    }
+
+    #[test]
+    fn composite_key_non_copy_column() {
+        let v = vec![(1, "a".to_string()), (1, "b".to_string()), (2, "a".to_string())];
+        let w = vec![(1, "b".to_string(), 'x'), (2, "a".to_string(), 'y')];
+
+        let rows = v.into_iter().join(w, composite_key!(|x| x.0, |x| x.1), composite_key!(|x| x.0, |x| x.1))
+            .map(|(_, (_, _, c))| c)
+            .collect::<String>();
+        assert_eq!( "xy", rows );
+    }
+
+    impl HasKey<u32> for Employee {
+        fn key(&self) -> u32 {
+            self.emp_id
+        }
+    }
+
+    impl HasKey<u32> for Badge {
+        fn key(&self) -> u32 {
+            self.emp_id
+        }
+    }
+
+    #[test]
+    fn join_by_key() {
+        let staff = vec![employee(1, 1, "ann"), employee(1, 2, "bo"), employee(2, 4, "cy")];
+        let badges = vec![badge(1, 2, 12), badge(2, 3, 23), badge(2, 4, 24)];
+
+        let rows = staff.iter().join_by_key(badges.iter())
+            .map(|(e, b)| (e.name, b.code))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("bo", 12), ("cy", 24)], rows );
+
+        let rows = staff.clone().join_by_key(badges.clone()).collect::<Vec<_>>();
+        assert_eq!( vec![(staff[1].clone(), badges[0].clone()), (staff[2].clone(), badges[2].clone())], rows );
+    }
+
+    #[test]
+    fn join_ref_borrows_string_keys() {
+        let v = vec![("ann".to_string(), 1), ("bo".to_string(), 2), ("cy".to_string(), 3)];
+        let w = vec![("bo".to_string(), 20), ("bo".to_string(), 21), ("di".to_string(), 40)];
+
+        let rows = v.join_ref(w, |(k,_)| k.as_str(), |(k,_)| k.as_str())
+            .map(|((k,a),(_,b))| (k, a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("bo".to_string(), 2, 20), ("bo".to_string(), 2, 21)], rows );
+    }
+
+    #[test]
+    fn join_ref_duplicate_keys_on_both_sides() {
+        let v = vec![(1,'a'), (2,'b'), (2,'c')];
+        let w = vec![(2,20), (2,21), (3,30)];
+
+        let rows = v.join_ref(w, |(k,_)| k, |(k,_)| k)
+            .map(|((_,a),(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('b',20), ('b',21), ('c',20), ('c',21)], rows );
+
+        let empty: Vec<(u32,char)> = vec![];
+        let rows = empty.join_ref(vec![(1,10)], |(k,_)| k, |(k,_)| k);
+        assert_eq!( 0, rows.count() );
+    }
+
+    #[test]
+    fn join_it_ref_compares_str_keys() {
+        let v = ["ann".to_string(), "bo".to_string(), "bo".to_string(), "di".to_string()];
+        let w = ["bo".to_string(), "cy".to_string(), "di".to_string()];
+
+        let mut rows = Vec::new();
+        join_it_ref(v.iter(), w.iter(), |s: &&String| s.as_str(), |s: &&String| s.as_str(), |a, b| rows.push((a.clone(), b.len())));
+        assert_eq!( vec![("bo".to_string(), 2), ("bo".to_string(), 2), ("di".to_string(), 2)], rows );
+    }
+}
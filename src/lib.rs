@@ -21,25 +21,264 @@
 //! }
 //! ```
 //!
+//! The items have to be `Clone`, since the key extractors take them by value while the join still
+//! needs to hold on to them until their side advances. Items are cloned whenever their key is
+//! extracted, so iterating by reference, as in the example above, keeps joins cheap for rows that
+//! are expensive to clone and makes it possible to join rows that can't be cloned at all.
+//!
+//! When both sequences are double ended, so is the join, and `.rev()` yields its rows from the
+//! last key back to the first.
+//!
+//! `join_by` takes a single comparator between a left and a right item instead of two key
+//! extractors, for keys that aren't `Ord` or need a custom ordering. `join_with_cmp` keeps the two
+//! key extractors but orders the keys with a comparator, like a descending or locale-aware order.
+//! `join_by_keys` takes a comparator per column of a composite key, as many as needed. `join_ref`
+//! takes key extractors borrowing the keys from the items, comparing `String` keys as `&str`
+//! without cloning them. `join_by_key` takes no key extractors at all, using the keys that the
+//! items of both sides expose by implementing `HasKey`.
+//!
+//! `join_filter` skips the joined rows rejected by a predicate on the pair of items, `join_map`
+//! combines the pair into a single value and `join_with_key` yields the key of each row along with
+//! its items. `join_chunks` hands out the rows in batches of a fixed size. `join_within` matches
+//! keys that are at most a tolerance apart instead of equal, and `asof_join` pairs every left item
+//! with the last right item at or before its key. `incremental_join` steps through the right items
+//! instead, caching the left item of the last key matched, for a right side that grows over time.
+//! `temporal_join` matches timestamps within a window, pairing each left item with all, the first
+//! or the nearest right item in it. `join3` joins three sequences at once, yielding triples of
+//! items, and `join_all` any number of sequences of the same type, yielding a `Vec` of items per
+//! row. `kmerge` merges any number of sorted sequences into one, without joining them, and
+//! `k_way_merge_join` groups the merged items by key, yielding a `Vec` of the items of any of the
+//! sequences sharing a key.
+//!
+//! `join_slice_gallop` joins a sorted slice with a sorted sequence, searching the slice for the key
+//! of each right item instead of stepping through it, which pays off when the right side is sparse.
+//! `join_indices` joins two sorted slices in place, yielding the indices of the joined items.
+//!
+//! `then_join` joins the rows of a join with yet another sequence, appending its items to the rows
+//! rather than nesting tuples, so `a.join(b, ..).then_join(c, ..)` yields rows of `(A, B, C)`.
+//!
+//! # Composite keys
+//! Keys can be anything `Ord`, including tuples, which are ordered by their first element, then
+//! their second and so on. Joining on several columns is a matter of returning a tuple of them
+//! from the key extractors, with the sequences sorted by the columns in the same order. A column
+//! sorted in descending order is wrapped in `std::cmp::Reverse`. The `composite_key!` macro builds
+//! such an extractor from one accessor per column.
+//!
+//! Keys that are equivalent but spelled differently on the two sides, like strings in different
+//! cases, are normalized with `map_left_key` and `map_right_key` on a `JoinIt`, which transform the
+//! extracted keys before they're compared.
+//!
+//! The joins rely on the sequences being sorted, and quietly give wrong results if they aren't.
+//! In debug builds, `join` and its adaptors, `left_join`, `right_join`, `full_join`, `group_join`,
+//! `semi_join` and `anti_join`, along with `intersect` and `difference` built on the last two,
+//! panic whenever a key they advance to goes backwards. The other joins, like `join_within`,
+//! `asof_join`, the slice joins and `union`, don't check.
+//! `AssertSorted` panics on unsorted keys for any sequence, checking every item rather than only the
+//! ones the join gets to, and `join_checked` wraps both sides of a join in it. `Sorted` marks a sequence as
+//! sorted in its type, once checked or vouched for. For input that can't be trusted, `try_join`
+//! checks the order in release builds too, returning a `JoinError` instead of panicking.
+//! `assert_unique_by_key` panics on a key that repeats, for a side expected to hold every key only
+//! once, and `try_unique_by_key` returns a `JoinError` instead. Sequences that aren't sorted yet
+//! can be sorted and joined in one go by `sort_and_join`, or `into_sorted_join` on `Joinable`.
+//!
+//! Sequences sorted in descending key order are joined with `join_desc`. Both sequences have to
+//! be sorted in the same direction, see `Order`.
+//!
+//! # Outer joins
+//! `left_join` keeps every item of the left sequence, pairing it with `None` whenever the right
+//! sequence has no item with the same key. `right_join` does the same the other way around, and
+//! `full_join` keeps the items of both sequences in ascending key order. All three are driven by
+//! the same merge as the inner join. `left_join_or` fills in a right item made by a closure
+//! instead of `None`, and `partition_join` collects the matched rows and the left items without a
+//! match apart. `split_join` splits the rows of a full join three ways, into the matched rows and
+//! the items of either side without a match, and `diff_join` labels them as a `JoinDiff` of an old
+//! and a new sequence instead.
+//!
+//! `group_join` keeps every item of the left sequence too, but pairs it with a `Vec` of all the
+//! right items sharing its key instead of a row for each of them, leaving the `Vec` empty if there
+//! are none.
+//!
+//! # Semi and anti joins
+//! `semi_join` returns only the items of the left sequence whose key is present in the right
+//! sequence, without pairing them with anything. `anti_join` returns the ones whose key is missing.
+//!
+//! # Join builder
+//! `join_builder` takes the two sequences and their key extractors without committing to a kind
+//! of join. Options such as the `Order` are set on the `JoinBuilder`, and `inner`, `left`, `right`,
+//! `full`, `semi` or `anti` then give the iterator of that join.
+//!
+//! # Fallible iterators
+//! Sorted records read from files or the network often come as `Result`s. `TryJoinIt` joins two
+//! iterators of `Result`s by the keys of their `Ok` values and returns the first `Err` of either
+//! side, after which it ends. `try_join_it` runs the same join as an inner iteration, returning
+//! the error. Key extractors that can fail themselves, like ones parsing a field, are used with
+//! `try_join_by_key` instead.
+//!
+//! # Set operations
+//! Sorted sequences of `Ord` items can also be treated as sets, with every item being its own key.
+//! `intersect` returns the items of the left set which are also in the right one, `difference`
+//! those which aren't.
+//!
+//! `union` takes key extractors like the joins, yielding the items of both sequences in key order
+//! with the pairs sharing a key merged into one by a closure, while `sym_difference` yields only
+//! the items with a key missing from the other sequence. `merge_union` keeps every item of both
+//! sequences, like a `UNION ALL`, or optionally only one of each left and right pair sharing a key.
+//! `merge_intersection` yields the left items with a key found on the right, like a semi join, and
+//! `merge_difference` those without, like an anti join. `merge_symmetric_difference` diffs two
+//! sequences of the same items, tagging each item missing from the other sequence with its `Side`.
+//!
+//! The set operations taking key extractors also take an `Order` with their `with_order` method,
+//! for sequences sorted in descending key order.
+//!
 //! # Inner iteration
 //! You're also provided with a function that runs an inner iteration. `join_it` takes two
 //! `IntoIterator`s, their key extractor closures and finally a body closure to handle the joined
-//! rows.
+//! rows. `join_it_by`, `join3_it`, `full_join_it`, `semi_join_it` and `anti_join_it` do the same
+//! for the other joins, while `join_fold` threads an accumulator through the joined rows and
+//! returns it and `join_count` just counts them. `join_any` and `join_find` stop at the first row
+//! satisfying a predicate, and `join_it_until` as soon as its body returns `ControlFlow::Break`.
+//! `join_it_optional` joins sequences of `Option`s, skipping the `None` items on both sides.
+//! `join_it_observed` reports every item stepped past without a match to a second closure, as an
+//! `AdvanceEvent`. `join_it_ref` is the counterpart of `join_ref`, for key extractors borrowing the
+//! keys from the items.
+//!
+//! With the `rayon` feature, `par_join_it` works like `join_it` while splitting the join into
+//! partitions by key and running them on rayon's thread pool. `par_join` does the same for two
+//! slices, collecting the rows in key order.
+//!
+//! # Streams
+//! With the `futures` feature, `async_join` joins two sorted `Stream`s into a stream of the joined
+//! rows, like `join` does for iterators.
+//!
+//! # Unsorted sequences
+//! With the `std` feature, `hash_join` joins sequences that aren't sorted, collecting the smaller
+//! one into a `HashMap` by key and looking the items of the other one up in it. `unsorted_join` is
+//! its counterpart on `Joinable`.
 //!
 //! # The algorithm
-//! Given two sequences with the values sorted by a unique key, you're able to make a join between keys with
+//! Given two sequences with the values sorted by a key, you're able to make a join between keys with
 //! O(n) complexity. The keys of the current iterator values are compared. It's a match whenever
 //! the two keys are equal, whereby a tuple of the two values are returned as a result of a joined row.
 //! If the keys don't match, the lesser key's iterator will increment until they do or until one of
 //! the iterators returns `None`.
+//!
+//! Keys don't have to be unique. The run of right values sharing a key is buffered and paired with
+//! every left value of that key, so two left values and three right values with the same key give
+//! six joined rows.
+//! `dedup_by_key` collapses each run of items sharing a key to its first item, for joining with
+//! only one item per key on that side.
+//!
+//! `with_stats` counts the items a join steps past on either side, and the rows it returns, into a
+//! `JoinStats`, to see how much of the sequences a join got through. `with_positions` pairs the
+//! items of every row with their positions in their sequences. A join made `rejoinable` can be
+//! restarted to go over the same rows again.
+//!
+//! `collect_into_btreemap` collects the rows of a join into a `BTreeMap` by their key, and with the
+//! `std` feature `collect_into_map` into a `HashMap`. A key holds only the last of its rows.
+//! Going the other way, `join_maps` joins two `BTreeMap`s on their keys, without key extractors.
+//!
+//! # `no_std`
+//! The crate depends on `std` only through its default `std` feature. Without it the crate is
+//! `no_std` and needs just `alloc`, for the buffered run of right values sharing a key.
+//! `JoinError` then doesn't implement `std::error::Error`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+extern crate alloc;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "derive")]
+extern crate join_it_derive;
+#[cfg(all(test, feature = "futures"))]
+extern crate futures;
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
 
-use std::cmp::Ord;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::{ControlFlow, Sub};
+#[cfg(all(test, not(feature = "std")))]
+use std::string::{String, ToString};
 
+mod adaptors;
+mod band;
+mod builder;
+mod by;
+mod chain;
+mod collect;
+mod dedup;
+mod fallible;
+#[cfg(feature = "std")]
+mod hash;
+mod incremental;
+#[macro_use]
+mod key;
+mod merge;
+mod multi;
+mod outer;
+#[cfg(feature = "rayon")]
+mod par;
+mod rejoin;
+mod semi;
+mod set;
+mod slice;
+mod sorted;
+mod stats;
+#[cfg(feature = "futures")]
+mod stream;
 
-pub struct JoinIt<I, J, KI, KJ>
+use merge::{Keys, Merge, Row};
+use key::key_of;
+use set::identity;
+
+pub use adaptors::{JoinChunks, JoinFilter, JoinMap, JoinWithKey, KeyTransformed};
+pub use band::{temporal_join, JoinAsOf, JoinWithin, TemporalJoin, WindowJoinStrategy};
+pub use builder::JoinBuilder;
+pub use by::{join_it_by, join_with_cmp, JoinBy, JoinByKeys, JoinItCmp};
+pub use chain::{Append, JoinChain};
+pub use collect::{join_maps, JoinMaps};
+pub use dedup::{dedup_by_key, DedupByKey};
+pub use fallible::{try_join_it, Fallible, TryJoinByKey, TryJoinIt};
+#[cfg(feature = "std")]
+pub use hash::{hash_join, HashJoin};
+pub use incremental::IncrementalJoinIt;
+pub use key::{join_it_ref, HasKey, JoinByKey, JoinRef};
+#[cfg(feature = "derive")]
+pub use join_it_derive::HasKey;
+pub use merge::Order;
+pub use multi::{join3, join3_it, join_all, k_way_merge_join, kmerge, Join3It, JoinAll, KMerge, KWayJoinIt};
+pub use outer::{diff_join, full_join_it, split_join, DiffJoinIt, FullJoinIt, GroupJoinIt, JoinDiff, LeftJoinIt, LeftJoinOr, Partition, RightJoinIt, SplitJoin};
+#[cfg(feature = "rayon")]
+pub use par::{par_join, par_join_it};
+pub use rejoin::Rejoin;
+pub use semi::{anti_join_it, semi_join_it, AntiJoinIt, SemiJoinIt};
+pub use set::{Difference, Intersect, MergeDifferenceIt, MergeIntersectionIt, MergeSymDiffIt, MergeUnionIt, SymDiffIt, UnionIt};
+pub use slice::{join_indices, join_slice_gallop, JoinGallop, JoinIndices};
+pub use sorted::{assert_unique_by_key, sort_and_join, try_unique_by_key, AssertSorted, AssertUnique, JoinChecked, JoinError, Side, Sorted, SortedJoin, TryJoin, TryUnique};
+pub use stats::{join_it_observed, AdvanceEvent, JoinStats, JoinWithPos, JoinWithStats};
+#[cfg(feature = "futures")]
+pub use stream::{async_join, AsyncJoin};
+
+
+/// Inner join between two sorted iterators, created by `Joinable::join`.
+///
+/// The join isn't an `ExactSizeIterator`, even when both iterators are. The number of rows depends
+/// on the keys, and duplicate keys on both sides are joined many-to-many, so the rows can outnumber
+/// the items of the shorter side. The upper bound of `size_hint` is the product of the items left
+/// on both sides instead.
+pub struct JoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
 {
-    i: I,
-    j: J,
+    merge: Merge<I, J>,
     ki: KI,
     kj: KJ,
 }
@@ -47,6 +286,8 @@ pub struct JoinIt<I, J, KI, KJ>
 
 /// Maps f over the join between `i` and `j`, based on the key extractors `ki` and `kj`.
 ///
+/// f can't stop the join early, use `join_it_until` for a body returning `ControlFlow`.
+///
 /// ```
 /// use join_it::join_it;
 /// let v = vec![33,44,55,66].into_iter().enumerate();
@@ -59,91 +300,1501 @@ pub struct JoinIt<I, J, KI, KJ>
 pub fn join_it<I,J,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) where
     I: IntoIterator,
     J: IntoIterator,
-    I::Item: Copy,
-    J::Item: Copy,
+    I::Item: Clone,
+    J::Item: Clone,
     KI: Fn(I::Item) -> K,
     KJ: Fn(J::Item) -> K,
     F: FnMut(I::Item, J::Item),
     K: Ord
 {
-    use std::cmp::Ordering::*;
-    let mut i = i.into_iter();
-    let mut j = j.into_iter();
-    let mut row = (i.next(), j.next());
+    for (v, w) in i.join(j, ki, kj) {
+        f(v, w);
+    }
+}
+
+
+/// Folds f over the join between `i` and `j`, based on the key extractors `ki` and `kj`, starting
+/// with `init` and returning the final accumulated value.
+///
+/// ```
+/// use join_it::join_fold;
+/// let v = vec![(0,1),(1,2),(2,3)];
+/// let w = vec![(1,10),(2,20)];
+/// let sum = join_fold(v, w, |(k,_)| k, |(k,_)| k, 0, |acc, (_,a), (_,b)| acc + a*b);
+/// assert_eq!(2*10 + 3*20, sum);
+/// ```
+pub fn join_fold<I,J,K,KI,KJ,B,F>( i: I, j: J, ki: KI, kj: KJ, init: B, mut f: F ) -> B where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(B, I::Item, J::Item) -> B,
+    K: Ord
+{
+    i.join(j, ki, kj).fold(init, |acc, (v, w)| f(acc, v, w))
+}
+
+
+/// Counts the rows of the join between `i` and `j`, based on the key extractors `ki` and `kj`.
+///
+/// ```
+/// use join_it::join_count;
+/// let v = vec![(0,'a'),(1,'b'),(1,'c')];
+/// let w = vec![(1,10),(1,11),(2,20)];
+/// assert_eq!(4, join_count(v, w, |(k,_)| k, |(k,_)| k));
+/// ```
+pub fn join_count<I,J,K,KI,KJ>( i: I, j: J, ki: KI, kj: KJ ) -> usize where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    K: Ord
+{
+    i.join(j, ki, kj).count()
+}
+
+
+/// Tests whether `pred` holds for any row of the join between `i` and `j`, based on the key
+/// extractors `ki` and `kj`. The join stops at the first row satisfying `pred`.
+///
+/// ```
+/// use join_it::join_any;
+/// let v = vec![(0,1),(1,2),(2,3)];
+/// let w = vec![(1,10),(2,20)];
+/// assert!(join_any(v.clone(), w.clone(), |(k,_)| k, |(k,_)| k, |(_,a), (_,b)| a*b == 60));
+/// assert!(!join_any(v, w, |(k,_)| k, |(k,_)| k, |(_,a), (_,b)| a*b == 10));
+/// ```
+pub fn join_any<I,J,K,KI,KJ,P>( i: I, j: J, ki: KI, kj: KJ, mut pred: P ) -> bool where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    P: FnMut(I::Item, J::Item) -> bool,
+    K: Ord
+{
+    i.join(j, ki, kj).any(|(v, w)| pred(v, w))
+}
+
+
+/// Returns the first row of the join between `i` and `j`, based on the key extractors `ki` and
+/// `kj`, that satisfies `pred`. The join stops as soon as one is found.
+///
+/// ```
+/// use join_it::join_find;
+/// let v = vec![(0,1),(1,2),(2,3)];
+/// let w = vec![(1,10),(2,20)];
+/// assert_eq!(Some(((2,3),(2,20))), join_find(v, w, |(k,_)| k, |(k,_)| k, |&(_,a), &(_,b)| a*b > 20));
+/// ```
+pub fn join_find<I,J,K,KI,KJ,P>( i: I, j: J, ki: KI, kj: KJ, mut pred: P ) -> Option<(I::Item, J::Item)> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    P: FnMut(&I::Item, &J::Item) -> bool,
+    K: Ord
+{
+    i.join(j, ki, kj).find(|(v, w)| pred(v, w))
+}
+
+
+/// Maps f over the join between `i` and `j` like `join_it`, stopping as soon as f returns
+/// `ControlFlow::Break` and returning the value it breaks with.
+///
+/// ```
+/// use join_it::join_it_until;
+/// use std::ops::ControlFlow;
+/// let v = vec![(0,1),(1,2),(2,3),(3,4)];
+/// let w = vec![(1,10),(2,20),(3,30)];
+/// let mut sum = 0;
+/// let res = join_it_until(v, w, |(k,_)| k, |(k,_)| k, |(_,a), (_,b)| {
+///     sum += a*b;
+///     if sum > 50 { ControlFlow::Break(sum) } else { ControlFlow::Continue(()) }
+/// });
+/// assert_eq!(ControlFlow::Break(80), res);
+/// ```
+pub fn join_it_until<I,J,K,KI,KJ,B,F>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) -> ControlFlow<B> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> ControlFlow<B>,
+    K: Ord
+{
+    i.join(j, ki, kj).try_for_each(|(v, w)| f(v, w))
+}
+
+
+/// Maps f over the join between `i` and `j` like `join_it`, for sequences with gaps. The `None`
+/// items of either side are skipped, and the key extractors take the values of the `Some` items.
+///
+/// ```
+/// use join_it::join_it_optional;
+/// let v = vec![Some((0,'a')), None, Some((2,'c'))];
+/// let w = vec![None, Some((2,22)), Some((3,33))];
+/// join_it_optional(v, w, |(k,_)| k, |(k,_)| k, |(k0,a),(k1,b)| {
+///     assert_eq!((2,2), (k0,k1));
+///     println!("Join result: ({},{})", a, b);
+/// });
+/// ```
+pub fn join_it_optional<I,J,T,U,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, f: F ) where
+    I: IntoIterator<Item = Option<T>>,
+    J: IntoIterator<Item = Option<U>>,
+    T: Clone,
+    U: Clone,
+    KI: Fn(T) -> K,
+    KJ: Fn(U) -> K,
+    F: FnMut(T, U),
+    K: Ord
+{
+    join_it(i.into_iter().flatten(), j.into_iter().flatten(), ki, kj, f)
+}
+
+
+impl<I,J,KI,KJ,K> JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    /// Skips the items on both sides with a key before `target`, so the join resumes at the first
+    /// row with a key at or after it. The skipped items are dropped without being joined.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c'),(3,'d')];
+    /// let w = vec![(1,11),(2,22),(3,33)];
+    ///
+    /// let mut rows = v.join(w, |(k,_)| k, |(k,_)| k);
+    /// rows.seek(2);
+    /// assert_eq!(Some(((2,'c'),(2,22))), rows.next());
+    ///
+    /// rows.seek(10);
+    /// assert_eq!(None, rows.next());
+    /// ```
+    pub fn seek(&mut self, target: K) {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        self.merge.seek_by(|v| ki(v.clone()).cmp(&target), |w| kj(w.clone()).cmp(&target));
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut Keys(ki, kj)) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => self.merge.next_in_back_group(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+
+    /// Counts the joined rows without cloning the items of a duplicate key group for each of them.
+    fn count(self) -> usize {
+        let JoinIt { merge, mut ki, mut kj } = self;
+
+        merge.count_by(&mut Keys(&mut ki, &mut kj))
+    }
+}
+
+
+/// Joins from the back, yielding the rows in reverse order. The front and the back can be mixed,
+/// together yielding every row exactly once.
+///
+/// ```
+/// use join_it::Joinable;
+///
+/// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+/// let w = vec![(1,11),(2,22),(3,33)];
+///
+/// let rows = v.join(w, |(k,_)| k, |(k,_)| k).rev().collect::<Vec<_>>();
+/// assert_eq!(vec![((2,'c'),(2,22)), ((1,'b'),(1,11))], rows);
+/// ```
+impl<I,J,KI,KJ,K> DoubleEndedIterator for JoinIt<I,J,KI,KJ> where
+    I: DoubleEndedIterator,
+    J: DoubleEndedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        self.merge.next_back_with(&mut Keys(ki, kj))
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+
+pub trait Joinable
+    where Self: IntoIterator + Sized,
+          Self::Item: Clone
+{
+    /// Inner join, yielding a pair for every combination of items in `self` and `iter` with equal keys.
+    ///
+    /// Duplicate keys are joined many-to-many:
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(5,'a'),(5,'b')];
+    /// let w = vec![(5,50),(5,51),(6,60)];
+    ///
+    /// let rows = v.join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',50),('a',51),('b',50),('b',51)], rows);
+    /// ```
+    fn join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Starts a join of `self` and `iter` whose kind is picked afterwards, with options such as the
+    /// sort order set first. `join` is a shortcut for `join_builder(..).inner()`.
+    ///
+    /// ```
+    /// use join_it::{Joinable, Order};
+    ///
+    /// let v = vec![(2,'a'),(1,'b'),(0,'c')];
+    /// let w = vec![(2,22),(0,0)];
+    ///
+    /// let rows = v.join_builder(w, |(k,_)| k, |(k,_)| k)
+    ///     .with_order(Order::Descending)
+    ///     .left()
+    ///     .map(|((_,a),b)| (a, b.map(|(_,b)| b)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',Some(22)),('b',None),('c',Some(0))], rows);
+    /// ```
+    fn join_builder<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinBuilder<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Inner join like `join`, extracting the keys of both sides, which hold the same item type,
+    /// with the single extractor `key`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,'x'),(2,'y')];
+    ///
+    /// let rows = v.join_on(w, |(k,_)| k)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('b','x'),('c','y')], rows);
+    /// ```
+    fn join_on<J,KF,K>(self, iter: J, key: KF) -> JoinIt<Self::IntoIter,J::IntoIter,KF,KF> where
+        J: IntoIterator<Item = Self::Item>,
+        KF: FnMut(Self::Item) -> K + Clone;
+
+    /// Inner join like `join`, skipping the rows for which `pred` returns `false`.
+    ///
+    /// This is a theta join whose condition includes key equality: the keys still drive the merge,
+    /// so `pred` only sees the pairs sharing a key, and a rejected pair doesn't keep the other
+    /// pairs of its key group from being tried.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(0,0),(1,11),(2,22)];
+    ///
+    /// let rows = v.join_filter(w, |(k,_)| k, |(k,_)| k, |_, &(_,b)| b > 10)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('b',11),('c',22)], rows);
+    /// ```
+    fn join_filter<J,KI,KJ,K,P>(self, iter: J, ki: KI, kj: KJ, pred: P) -> JoinFilter<Self::IntoIter,J::IntoIter,KI,KJ,P> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        P: FnMut(&Self::Item, &J::Item) -> bool;
+
+    /// Inner join like `join`, yielding the result of `f` on the items of each row.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11),(2,22)];
+    ///
+    /// let rows = v.join_map(w, |(k,_)| k, |(k,_)| k, |(_,a),(_,b)| format!("{}{}", a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec!["b11","c22"], rows);
+    /// ```
+    fn join_map<J,KI,KJ,K,F,R>(self, iter: J, ki: KI, kj: KJ, f: F) -> JoinMap<Self::IntoIter,J::IntoIter,KI,KJ,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        F: FnMut(Self::Item, J::Item) -> R;
+
+    /// Inner join like `join`, yielding the key of each row along with its items.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11),(2,22)];
+    ///
+    /// let rows = v.join_with_key(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|(k,(_,a),(_,b))| (k, a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'b',11),(2,'c',22)], rows);
+    /// ```
+    fn join_with_key<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinWithKey<Self::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Inner join like `join`, yielding the rows in `Vec`s of `size` rows, the last one holding
+    /// the rows left over.
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(0,10),(1,11),(2,22)];
+    ///
+    /// let chunks = v.join_chunks(w, |(k,_)| k, |(k,_)| k, 2).collect::<Vec<_>>();
+    /// assert_eq!(vec![vec![((0,'a'),(0,10)), ((1,'b'),(1,11))], vec![((2,'c'),(2,22))]], chunks);
+    /// ```
+    fn join_chunks<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, size: usize) -> JoinChunks<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Inner join like `sort_and_join`, sorting both sequences by their keys before joining them.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(2,'c'),(1,'b')];
+    /// let w = vec![(2,22),(1,11)];
+    ///
+    /// let rows = v.into_sorted_join(w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![((1,'b'),(1,11)), ((2,'c'),(2,22))], rows);
+    /// ```
+    fn into_sorted_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SortedJoin<Self::Item,J::Item,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord;
+
+    /// Inner join like `join`, wrapping both sequences in `AssertSorted` to panic in debug builds
+    /// if either of them isn't sorted.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11),(2,22)];
+    ///
+    /// let rows = v.join_checked(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('b',11),('c',22)], rows);
+    /// ```
+    fn join_checked<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinChecked<Self::IntoIter,J::IntoIter,K,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K + Clone,
+        KJ: FnMut(J::Item) -> K + Clone,
+        K: Ord + fmt::Debug;
+
+    /// Inner join like `join`, validating that both sequences are sorted in ascending key order and
+    /// returning a `JoinError` if they aren't, in release builds too.
+    ///
+    /// ```
+    /// use join_it::{JoinError, Joinable, Side};
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(2,22),(1,11)];
+    ///
+    /// let rows = v.try_join(w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![Ok(((2,'c'),(2,22))), Err(JoinError::Unsorted { side: Side::Right, key: 1 })], rows);
+    /// ```
+    fn try_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> TryJoin<Self::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K + Clone,
+        KJ: FnMut(J::Item) -> K + Clone,
+        K: Ord;
+
+    /// Inner join like `join`, with key extractors that may fail. The first error of either of them
+    /// is returned, ending the join.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec!["1","2","x"];
+    /// let w = vec!["1","2","3"];
+    ///
+    /// let rows = v.try_join_by_key(w, |k| k.parse::<u32>(), |k| k.parse::<u32>()).collect::<Vec<_>>();
+    /// assert_eq!(3, rows.len());
+    /// assert!(rows[2].is_err());
+    /// ```
+    fn try_join_by_key<J,KI,KJ,K,E>(self, iter: J, ki: KI, kj: KJ) -> TryJoinByKey<Self::IntoIter,J::IntoIter,KI,KJ,K,E> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> Result<K, E>,
+        KJ: FnMut(J::Item) -> Result<K, E>;
+
+    /// Inner join of three sequences sorted by a common key, yielding a row for every combination
+    /// of items with a key found in all three of them.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let orders = vec![(1,"o1"),(2,"o2"),(3,"o3")];
+    /// let customers = vec![(1,"alice"),(2,"bob"),(3,"carol")];
+    /// let regions = vec![(1,"north"),(3,"west")];
+    ///
+    /// let rows = orders.join3(customers, regions, |(k,_)| k, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,o),(_,c),(_,r))| (o, c, r))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("o1","alice","north"),("o3","carol","west")], rows);
+    /// ```
+    fn join3<J,L,KI,KJ,KL,K>(self, iter: J, other: L, ki: KI, kj: KJ, kl: KL) -> Join3It<Self::IntoIter,J::IntoIter,L::IntoIter,KI,KJ,KL,K> where
+        J: IntoIterator,
+        L: IntoIterator,
+        J::Item: Clone,
+        L::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        KL: FnMut(L::Item) -> K,
+        K: Ord + Clone;
+
+    /// Band join, pairing every item of `self` with each item of `iter` whose key is at most
+    /// `tolerance` away from its key.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(10,'a'),(20,'b')];
+    /// let w = vec![(9,'x'),(11,'y'),(15,'z')];
+    ///
+    /// let rows = v.join_within(w, |(k,_)| k, |(k,_)| k, 1)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a','x'),('a','y')], rows);
+    /// ```
+    fn join_within<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, tolerance: K) -> JoinWithin<Self::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord + Sub<Output = K> + Clone;
+
+    /// As-of join, pairing every item of `self` with the last item of `iter` whose key is at or
+    /// before its key, or `None` if there is none.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let trades = vec![(1,'a'),(4,'b'),(6,'c')];
+    /// let quotes = vec![(2,10),(3,11),(6,12)];
+    ///
+    /// let rows = trades.asof_join(quotes, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,t),q)| (t, q.map(|(_,q)| q)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',None),('b',Some(11)),('c',Some(12))], rows);
+    /// ```
+    fn asof_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinAsOf<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord;
+
+    /// Incremental join, pairing every item of `iter` with the item of `self` sharing its key,
+    /// caching the last matched item of `self` for the items of `iter` right after it.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let users = vec![(1,"ann"),(2,"bo")];
+    /// let events = vec![(1,'a'),(1,'b'),(2,'c'),(3,'d')];
+    ///
+    /// let rows = users.incremental_join(events, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,u),(_,e))| (u, e))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("ann",'a'),("ann",'b'),("bo",'c')], rows);
+    /// ```
+    fn incremental_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> IncrementalJoinIt<Self::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord;
+
+    /// Inner join like `hash_join`, for sequences that aren't sorted by their key.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(2,'c'),(1,'b')];
+    /// let w = vec![(1,11),(2,22),(1,12)];
+    ///
+    /// let rows = v.unsorted_join(w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![((1,'b'),(1,11)), ((2,'c'),(2,22)), ((1,'b'),(1,12))], rows);
+    /// ```
+    #[cfg(feature = "std")]
+    fn unsorted_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> HashJoin<Self::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: std::hash::Hash + Eq;
+
+    /// Inner join like `join`, using the keys the items of both sides expose through `HasKey`.
+    ///
+    /// ```
+    /// use join_it::{HasKey, Joinable};
+    ///
+    /// struct User { id: u32, name: &'static str }
+    /// struct Order { user_id: u32, item: &'static str }
+    ///
+    /// impl HasKey<u32> for User {
+    ///     fn key(&self) -> u32 { self.id }
+    /// }
+    ///
+    /// impl HasKey<u32> for Order {
+    ///     fn key(&self) -> u32 { self.user_id }
+    /// }
+    ///
+    /// let users = vec![User { id: 1, name: "ann" }, User { id: 2, name: "bo" }];
+    /// let orders = vec![Order { user_id: 2, item: "pen" }];
+    ///
+    /// let rows = users.iter().join_by_key(orders.iter())
+    ///     .map(|(u, o)| (u.name, o.item))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("bo","pen")], rows);
+    /// ```
+    fn join_by_key<J,K>(self, iter: J) -> JoinByKey<Self::IntoIter,J::IntoIter,K> where
+        J: IntoIterator,
+        J::Item: Clone + HasKey<K>,
+        Self::Item: HasKey<K>,
+        K: Ord;
+
+    /// Inner join like `join`, of sequences sorted in descending key order.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(2,'c'),(1,'b'),(0,'a')];
+    /// let w = vec![(3,33),(2,22),(0,0)];
+    ///
+    /// let rows = v.join_desc(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('c',22),('a',0)], rows);
+    /// ```
+    fn join_desc<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Inner join of sequences sorted by `cmp`, matching the items for which it returns `Equal`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0.5f64,'a'),(1.5,'b')];
+    /// let w = vec![(1.5f64,11),(2.5,22)];
+    ///
+    /// let rows = v.join_by(w, |a, b| a.0.total_cmp(&b.0))
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('b',11)], rows);
+    /// ```
+    fn join_by<J,F>(self, iter: J, cmp: F) -> JoinBy<Self::IntoIter,J::IntoIter,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        F: FnMut(&Self::Item, &J::Item) -> Ordering;
+
+    /// Inner join like `join_by`, comparing the items column by column with the comparators in
+    /// `keys`. The first column that isn't `Equal` decides, so the sequences have to be sorted by
+    /// the columns in the order of `keys`.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use join_it::Joinable;
+    ///
+    /// let sales = vec![("eu",2023,5),("eu",2024,7),("us",2024,9)];
+    /// let targets = vec![("eu",2024,6),("us",2024,8)];
+    ///
+    /// let keys: [fn(&(&str,u32,u32), &(&str,u32,u32)) -> Ordering; 2] = [
+    ///     |a, b| a.0.cmp(b.0),
+    ///     |a, b| a.1.cmp(&b.1),
+    /// ];
+    /// let rows = sales.join_by_keys(targets, &keys)
+    ///     .map(|(a, b)| (a.0, a.2, b.2))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("eu",7,6),("us",9,8)], rows);
+    /// ```
+    fn join_by_keys<'a,J,F>(self, iter: J, keys: &'a [F]) -> JoinByKeys<'a,Self::IntoIter,J::IntoIter,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        F: Fn(&Self::Item, &J::Item) -> Ordering;
+
+    /// Inner join like `join`, with key extractors borrowing the keys from the items instead of
+    /// returning them by value.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![("ann".to_string(),1),("bo".to_string(),2)];
+    /// let w = vec![("bo".to_string(),20),("cy".to_string(),30)];
+    ///
+    /// let rows = v.join_ref(w, |(k,_)| k.as_str(), |(k,_)| k.as_str())
+    ///     .map(|((k,a),(_,b))| (k, a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![("bo".to_string(),2,20)], rows);
+    /// ```
+    fn join_ref<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinRef<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(&Self::Item) -> &K,
+        KJ: FnMut(&J::Item) -> &K,
+        K: Ord + ?Sized;
+
+    /// Left outer join, yielding every item of `self` along with its match in `iter`, if any.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11)];
+    ///
+    /// let rows = v.left_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,a),b)| (a, b.map(|(_,b)| b)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',None),('b',Some(11)),('c',None)], rows);
+    /// ```
+    fn left_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> LeftJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Left outer join like `left_join`, pairing the items of `self` without a match with a right
+    /// item made by `default` instead of `None`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b')];
+    /// let w = vec![(1,11)];
+    ///
+    /// let rows = v.left_join_or(w, |(k,_)| k, |(k,_)| k, || (0,0))
+    ///     .map(|((_,a),(_,b))| (a, b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',0),('b',11)], rows);
+    /// ```
+    fn left_join_or<J,KI,KJ,K,D>(self, iter: J, ki: KI, kj: KJ, default: D) -> LeftJoinOr<Self::IntoIter,J::IntoIter,KI,KJ,D> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        D: FnMut() -> J::Item;
+
+    /// Runs a left join, splitting its rows into the pairs of the inner join and the items of `self`
+    /// without a match.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11)];
+    ///
+    /// let (matched, unmatched) = v.partition_join(w, |(k,_)| k, |(k,_)| k);
+    /// assert_eq!(vec![((1,'b'),(1,11))], matched);
+    /// assert_eq!(vec![(0,'a'),(2,'c')], unmatched);
+    /// ```
+    fn partition_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> Partition<Self::Item,J::Item> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord;
+
+    /// Right outer join, yielding every item of `iter` along with its match in `self`, if any.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'b')];
+    /// let w = vec![(0,0),(1,11),(2,22)];
+    ///
+    /// let rows = v.right_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|(a,(_,b))| (a.map(|(_,a)| a), b))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![(None,0),(Some('b'),11),(None,22)], rows);
+    /// ```
+    fn right_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> RightJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Full outer join, yielding every item of both `self` and `iter`, paired up where the keys match.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b')];
+    /// let w = vec![(1,11),(2,22)];
+    ///
+    /// let rows = v.full_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|(a,b)| (a.map(|(_,a)| a), b.map(|(_,b)| b)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![(Some('a'),None),(Some('b'),Some(11)),(None,Some(22))], rows);
+    /// ```
+    fn full_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> FullJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Group join, yielding every item of `self` along with all of its matches in `iter`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b')];
+    /// let w = vec![(1,11),(1,12),(2,22)];
+    ///
+    /// let rows = v.group_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|((_,a),bs)| (a, bs.into_iter().map(|(_,b)| b).collect::<Vec<_>>()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec![('a',vec![]),('b',vec![11,12])], rows);
+    /// ```
+    fn group_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> GroupJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Semi join, yielding the items of `self` which have at least one match in `iter`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11),(1,12)];
+    ///
+    /// let rows = v.semi_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|(_,a)| a)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec!['b'], rows);
+    /// ```
+    fn semi_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SemiJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Anti join, yielding the items of `self` which have no match in `iter`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11)];
+    ///
+    /// let rows = v.anti_join(w, |(k,_)| k, |(k,_)| k)
+    ///     .map(|(_,a)| a)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(vec!['a','c'], rows);
+    /// ```
+    fn anti_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> AntiJoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Intersection of two sorted sets, yielding the items of `self` that are also in `iter`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![1,2,4,5];
+    /// let w = vec![2,3,5];
+    ///
+    /// assert_eq!(vec![2,5], v.intersect(w).collect::<Vec<_>>());
+    /// ```
+    fn intersect<J>(self, iter: J) -> Intersect<Self::IntoIter,J::IntoIter> where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord;
+
+    /// Difference of two sorted sets, yielding the items of `self` that aren't in `iter`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![1,2,4,5];
+    /// let w = vec![2,3,5];
+    ///
+    /// assert_eq!(vec![1,4], v.difference(w).collect::<Vec<_>>());
+    /// ```
+    fn difference<J>(self, iter: J) -> Difference<Self::IntoIter,J::IntoIter> where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord;
+
+    /// Union of two sorted sequences, yielding every key of either of them once in sorted order.
+    ///
+    /// Items only found on one side are converted into the output with `Into`, pairs of a left and
+    /// a right item sharing a key are combined by `f`, so a union of two sets of the same type can
+    /// simply keep one of them.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'a'),(2,'b')];
+    /// let w = vec![(2,'x'),(3,'y')];
+    ///
+    /// let rows = v.union(w, |(k,_)| k, |(k,_)| k, |v, _| v).collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'a'),(2,'b'),(3,'y')], rows);
+    /// ```
+    fn union<J,KI,KJ,K,F,C>(self, iter: J, ki: KI, kj: KJ, f: F) -> UnionIt<Self::IntoIter,J::IntoIter,KI,KJ,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        F: FnMut(Self::Item, J::Item) -> C;
+
+    /// Symmetric difference of two sorted sequences, yielding the items of either of them with a key
+    /// missing from the other one in sorted order.
+    ///
+    /// The items of `iter` are converted into the items of `self` with `Into`, so sets of the same
+    /// type are merged as they are.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![1,2,4,5];
+    /// let w = vec![2,3,5];
+    ///
+    /// assert_eq!(vec![1,3,4], v.sym_difference(w, |x| x, |x| x).collect::<Vec<_>>());
+    /// ```
+    fn sym_difference<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SymDiffIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Merge of two sorted sequences, yielding every item of both of them in sorted order.
+    ///
+    /// Left items come before right items sharing their key. With `dedup` set, a right item sharing
+    /// its key with a left one is dropped instead, keeping a single item per pair. The items of
+    /// `iter` are converted into the items of `self` with `Into`.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'a'),(2,'b')];
+    /// let w = vec![(2,'x'),(3,'y')];
+    ///
+    /// let all = v.clone().merge_union(w.clone(), |(k,_)| k, |(k,_)| k, false).collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'a'),(2,'b'),(2,'x'),(3,'y')], all);
+    ///
+    /// let distinct = v.merge_union(w, |(k,_)| k, |(k,_)| k, true).collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'a'),(2,'b'),(3,'y')], distinct);
+    /// ```
+    fn merge_union<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, dedup: bool) -> MergeUnionIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Intersection of two sorted sequences by key, yielding the items of `self` with a key also
+    /// found in `iter`.
+    ///
+    /// Unlike `intersect`, the items don't have to be their own keys. Every left item with a
+    /// matching key is returned, so duplicate keys on the left are kept.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'a'),(2,'b'),(2,'c'),(4,'d')];
+    /// let w = vec![2,3,4];
+    ///
+    /// let rows = v.merge_intersection(w, |(k,_)| k, |k| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![(2,'b'),(2,'c'),(4,'d')], rows);
+    /// ```
+    fn merge_intersection<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> MergeIntersectionIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Difference of two sorted sequences by key, yielding the items of `self` with a key missing
+    /// from `iter`.
+    ///
+    /// Unlike `difference`, the items don't have to be their own keys. Every left item without a
+    /// matching key is returned, so duplicate keys on the left are kept.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(1,'a'),(2,'b'),(2,'c'),(4,'d')];
+    /// let w = vec![2,3];
+    ///
+    /// let rows = v.merge_difference(w, |(k,_)| k, |k| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'a'),(4,'d')], rows);
+    /// ```
+    fn merge_difference<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> MergeDifferenceIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K;
+
+    /// Symmetric difference of two sorted sequences of the same items, yielding each item with a
+    /// key missing from the other sequence along with the `Side` it comes from.
+    ///
+    /// ```
+    /// use join_it::{Joinable, Side};
+    ///
+    /// let before = vec![(1,'a'),(2,'b'),(3,'c')];
+    /// let after = vec![(1,'a'),(3,'c'),(4,'d')];
+    ///
+    /// let changes = before.merge_symmetric_difference(after, |(k,_)| k).collect::<Vec<_>>();
+    /// assert_eq!(vec![(Side::Left,(2,'b')),(Side::Right,(4,'d'))], changes);
+    /// ```
+    fn merge_symmetric_difference<J,KF,K>(self, iter: J, key: KF) -> MergeSymDiffIt<Self::IntoIter,J::IntoIter,KF> where
+        J: IntoIterator<Item = Self::Item>,
+        KF: FnMut(Self::Item) -> K;
+}
+
+
+
+impl<I> Joinable for I where
+    I: IntoIterator,
+    I::Item: Clone
+{
+    fn join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).inner()
+    }
+
+    fn join_builder<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinBuilder<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        JoinBuilder {
+            i: self.into_iter(),
+            j: iter.into_iter(),
+            ki,
+            kj,
+            order: Order::Ascending,
+        }
+    }
+
+    fn join_on<J,KF,K>(self, iter: J, key: KF) -> JoinIt<I::IntoIter,J::IntoIter,KF,KF> where
+        J: IntoIterator<Item = Self::Item>,
+        KF: FnMut(Self::Item) -> K + Clone,
+    {
+        self.join(iter, key.clone(), key)
+    }
+
+    fn join_filter<J,KI,KJ,K,P>(self, iter: J, ki: KI, kj: KJ, pred: P) -> JoinFilter<I::IntoIter,J::IntoIter,KI,KJ,P> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        P: FnMut(&Self::Item, &J::Item) -> bool,
+    {
+        JoinFilter {
+            join: self.join(iter, ki, kj),
+            pred,
+        }
+    }
+
+    fn join_map<J,KI,KJ,K,F,R>(self, iter: J, ki: KI, kj: KJ, f: F) -> JoinMap<I::IntoIter,J::IntoIter,KI,KJ,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        F: FnMut(Self::Item, J::Item) -> R,
+    {
+        JoinMap {
+            join: self.join(iter, ki, kj),
+            f,
+        }
+    }
+
+    fn join_with_key<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinWithKey<I::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        JoinWithKey {
+            merge: Merge::new(self.into_iter(), iter.into_iter(), false, false),
+            ki,
+            kj,
+            key: None,
+        }
+    }
+
+    fn join_chunks<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, size: usize) -> JoinChunks<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        assert!(size != 0, "join_it: the chunk size has to be non-zero");
+
+        JoinChunks {
+            join: self.join(iter, ki, kj),
+            size,
+        }
+    }
+
+    fn into_sorted_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SortedJoin<Self::Item,J::Item,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord,
+    {
+        sort_and_join(self, iter, ki, kj)
+    }
+
+    fn join_checked<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinChecked<I::IntoIter,J::IntoIter,K,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K + Clone,
+        KJ: FnMut(J::Item) -> K + Clone,
+        K: Ord + fmt::Debug,
+    {
+        AssertSorted::new(self, ki.clone()).join(AssertSorted::new(iter, kj.clone()), ki, kj)
+    }
+
+    fn try_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> TryJoin<I::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K + Clone,
+        KJ: FnMut(J::Item) -> K + Clone,
+        K: Ord,
+    {
+        TryJoin::new(self.into_iter(), iter.into_iter(), ki, kj)
+    }
+
+    fn try_join_by_key<J,KI,KJ,K,E>(self, iter: J, ki: KI, kj: KJ) -> TryJoinByKey<I::IntoIter,J::IntoIter,KI,KJ,K,E> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> Result<K, E>,
+        KJ: FnMut(J::Item) -> Result<K, E>,
+    {
+        TryJoinByKey::new(self.into_iter(), iter.into_iter(), ki, kj)
+    }
+
+    fn join3<J,L,KI,KJ,KL,K>(self, iter: J, other: L, ki: KI, kj: KJ, kl: KL) -> Join3It<I::IntoIter,J::IntoIter,L::IntoIter,KI,KJ,KL,K> where
+        J: IntoIterator,
+        L: IntoIterator,
+        J::Item: Clone,
+        L::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        KL: FnMut(L::Item) -> K,
+        K: Ord + Clone,
+    {
+        Join3It {
+            merge: Merge::new(self.join_with_key(iter, ki, kj), other.into_iter(), false, false),
+            kl,
+        }
+    }
+
+    fn join_within<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, tolerance: K) -> JoinWithin<I::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord + Sub<Output = K> + Clone,
+    {
+        JoinWithin {
+            i: self.into_iter(),
+            j: iter.into_iter().peekable(),
+            ki,
+            kj,
+            tolerance,
+            curr: None,
+            window: VecDeque::new(),
+            pos: 0,
+        }
+    }
+
+    fn asof_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinAsOf<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord,
+    {
+        JoinAsOf {
+            i: self.into_iter(),
+            j: iter.into_iter().peekable(),
+            ki,
+            kj,
+            last: None,
+        }
+    }
+
+    fn incremental_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> IncrementalJoinIt<I::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord,
+    {
+        IncrementalJoinIt {
+            i: self.into_iter(),
+            j: iter.into_iter(),
+            ki,
+            kj,
+            cached_left: None,
+            cached_key: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn unsorted_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> HashJoin<I::IntoIter,J::IntoIter,KI,KJ,K> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: std::hash::Hash + Eq,
+    {
+        hash_join(self, iter, ki, kj)
+    }
+
+    fn join_by_key<J,K>(self, iter: J) -> JoinByKey<I::IntoIter,J::IntoIter,K> where
+        J: IntoIterator,
+        J::Item: Clone + HasKey<K>,
+        Self::Item: HasKey<K>,
+        K: Ord,
+    {
+        self.join(iter, key_of as fn(_) -> _, key_of as fn(_) -> _)
+    }
+
+    fn join_desc<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).with_order(Order::Descending).inner()
+    }
+
+    fn join_by<J,F>(self, iter: J, cmp: F) -> JoinBy<I::IntoIter,J::IntoIter,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        F: FnMut(&Self::Item, &J::Item) -> Ordering,
+    {
+        JoinBy {
+            merge: Merge::new(self.into_iter(), iter.into_iter(), false, false),
+            cmp,
+        }
+    }
+
+    fn join_by_keys<'a,J,F>(self, iter: J, keys: &'a [F]) -> JoinByKeys<'a,I::IntoIter,J::IntoIter,F> where
+        J: IntoIterator,
+        J::Item: Clone,
+        F: Fn(&Self::Item, &J::Item) -> Ordering,
+    {
+        JoinByKeys {
+            merge: Merge::new(self.into_iter(), iter.into_iter(), false, false),
+            keys,
+        }
+    }
+
+    fn join_ref<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinRef<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(&Self::Item) -> &K,
+        KJ: FnMut(&J::Item) -> &K,
+        K: Ord + ?Sized,
+    {
+        JoinRef {
+            merge: Merge::new(self.into_iter(), iter.into_iter(), false, false),
+            ki,
+            kj,
+        }
+    }
+
+    fn left_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> LeftJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).left()
+    }
 
-    while let (Some(v), Some(w)) = row {
-        match Ord::cmp(&ki(v), &kj(w)) {
-            Less => row = (i.next(), Some(w)),
-            Greater => row = (Some(v), j.next()),
-            Equal => {
-                f(v, w);
-                row = (i.next(), j.next());
-            },
+    fn left_join_or<J,KI,KJ,K,D>(self, iter: J, ki: KI, kj: KJ, default: D) -> LeftJoinOr<I::IntoIter,J::IntoIter,KI,KJ,D> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        D: FnMut() -> J::Item,
+    {
+        LeftJoinOr {
+            join: self.left_join(iter, ki, kj),
+            default,
         }
     }
-}
 
+    fn partition_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> Partition<Self::Item,J::Item> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+        K: Ord,
+    {
+        let mut matched = Vec::new();
+        let mut unmatched = Vec::new();
 
-impl<I,J,KI,KJ,K> Iterator for JoinIt<I,J,KI,KJ> where
-    I: Iterator,
-    J: Iterator,
-    I::Item: Copy,
-    J::Item: Copy,
-    KI: FnMut(I::Item) -> K,
-    KJ: FnMut(J::Item) -> K,
-    K: Ord
-{
-    type Item = (I::Item, J::Item);
+        for (v, w) in self.left_join(iter, ki, kj) {
+            match w {
+                Some(w) => matched.push((v, w)),
+                None => unmatched.push(v),
+            }
+        }
+        (matched, unmatched)
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        use std::cmp::Ordering::*;
+    fn right_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> RightJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).right()
+    }
 
-        let mut row = (self.i.next(), self.j.next());
+    fn full_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> FullJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).full()
+    }
 
-        while let (Some(v), Some(w)) = row {
-            match Ord::cmp(&(self.ki)(v), &(self.kj)(w)) {
-                Less => row = (self.i.next(), Some(w)),
-                Greater => row = (Some(v), self.j.next()),
-                Equal => {
-                    return Some((v, w));
-                },
-            }
+    fn group_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> GroupJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        GroupJoinIt {
+            merge: Merge::new(self.into_iter(), iter.into_iter(), true, false),
+            ki,
+            kj,
         }
+    }
+
+    fn semi_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SemiJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).semi()
+    }
 
-        None
+    fn anti_join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> AntiJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).anti()
     }
-}
 
+    fn intersect<J>(self, iter: J) -> Intersect<I::IntoIter,J::IntoIter> where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        self.semi_join(iter, identity as fn(_) -> _, identity as fn(_) -> _)
+    }
 
+    fn difference<J>(self, iter: J) -> Difference<I::IntoIter,J::IntoIter> where
+        J: IntoIterator<Item = Self::Item>,
+        Self::Item: Ord,
+    {
+        self.anti_join(iter, identity as fn(_) -> _, identity as fn(_) -> _)
+    }
 
-pub trait Joinable
-    where Self: IntoIterator + Sized,
-          Self::Item: Copy
-{
-    fn join<J,KI,KJ,K>(self, J, KI, KJ) -> JoinIt<Self::IntoIter,J::IntoIter,KI,KJ> where
+    fn union<J,KI,KJ,K,F,C>(self, iter: J, ki: KI, kj: KJ, f: F) -> UnionIt<I::IntoIter,J::IntoIter,KI,KJ,F> where
         J: IntoIterator,
-        J::Item: Copy,
+        J::Item: Clone,
         KI: FnMut(Self::Item) -> K,
-        KJ: FnMut(J::Item) -> K;
-}
+        KJ: FnMut(J::Item) -> K,
+        F: FnMut(Self::Item, J::Item) -> C,
+    {
+        UnionIt {
+            i: self.into_iter().peekable(),
+            j: iter.into_iter().peekable(),
+            ki,
+            kj,
+            f,
+            order: Order::Ascending,
+        }
+    }
+
+    fn sym_difference<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> SymDiffIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        SymDiffIt {
+            i: self.into_iter().peekable(),
+            j: iter.into_iter().peekable(),
+            ki,
+            kj,
+            order: Order::Ascending,
+        }
+    }
 
+    fn merge_union<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ, dedup: bool) -> MergeUnionIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        MergeUnionIt {
+            i: self.into_iter().peekable(),
+            j: iter.into_iter().peekable(),
+            ki,
+            kj,
+            dedup,
+            order: Order::Ascending,
+        }
+    }
 
+    fn merge_intersection<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> MergeIntersectionIt<I::IntoIter,J::IntoIter,KI,KJ> where
+        J: IntoIterator,
+        J::Item: Clone,
+        KI: FnMut(Self::Item) -> K,
+        KJ: FnMut(J::Item) -> K,
+    {
+        self.join_builder(iter, ki, kj).semi()
+    }
 
-impl<I> Joinable for I where
-    I: IntoIterator,
-    I::Item: Copy
-{
-    fn join<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> JoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+    fn merge_difference<J,KI,KJ,K>(self, iter: J, ki: KI, kj: KJ) -> MergeDifferenceIt<I::IntoIter,J::IntoIter,KI,KJ> where
         J: IntoIterator,
-        J::Item: Copy,
+        J::Item: Clone,
         KI: FnMut(Self::Item) -> K,
         KJ: FnMut(J::Item) -> K,
     {
-        JoinIt {
-            i: self.into_iter(),
-            j: iter.into_iter(),
-            ki: ki,
-            kj: kj
+        self.join_builder(iter, ki, kj).anti()
+    }
+
+    fn merge_symmetric_difference<J,KF,K>(self, iter: J, key: KF) -> MergeSymDiffIt<I::IntoIter,J::IntoIter,KF> where
+        J: IntoIterator<Item = Self::Item>,
+        KF: FnMut(Self::Item) -> K,
+    {
+        MergeSymDiffIt {
+            i: self.into_iter().peekable(),
+            j: iter.into_iter().peekable(),
+            key,
+            order: Order::Ascending,
         }
     }
 }
@@ -154,10 +1805,10 @@ mod tests {
     use super::*;
     #[test]
     fn internal_iterator() {
-        let v = vec![(0,'a'), (1,'b'), (2,'c')];
+        let v = [(0,'a'), (1,'b'), (2,'c')];
         let it = v.iter(); // Iterator returning &({int}, char).
 
-        let w = vec![66, 77, 88];
+        let w = [66, 77, 88];
         let it2 =  w.iter().enumerate(); // Iterator returning ({int}, &{int}).
 
         let mut r = vec![];
@@ -170,10 +1821,10 @@ mod tests {
 
     #[test]
     fn move_iterators() {
-        let v = vec!['a', 'b', 'c'];
+        let v = ['a', 'b', 'c'];
         let it = v.iter().enumerate();
 
-        let w = vec![66, 77, 88];
+        let w = [66, 77, 88];
         let it2 =  w.iter().enumerate();
 
         let join_it = it.join(it2, |(x,_)| x, |(x,_)| x)
@@ -184,10 +1835,10 @@ mod tests {
 
     #[test]
     fn referencing_iterators() {
-        let v = vec![(0,'a'), (1,'b'), (2,'c')];
+        let v = [(0,'a'), (1,'b'), (2,'c')];
         let it = v.iter();
 
-        let w = vec![(0,66), (1,77), (2,88)];
+        let w = [(0,66), (1,77), (2,88)];
         let it2 =  w.iter();
 
         let join_it = it.join(it2, |&(x,_)| x, |&(x,_)| x)
@@ -199,10 +1850,10 @@ mod tests {
 
     #[test]
     fn key_jumping() {
-        let v = vec![(1,'b'), (2,'c'), (3,'d')];
+        let v = [(1,'b'), (2,'c'), (3,'d')];
         let it = v.iter();
 
-        let w = vec![(0,66), (1,77), (3,99), (4,11)];
+        let w = [(0,66), (1,77), (3,99), (4,11)];
         let it2 =  w.iter();
 
         let join_it = it.join(it2, |&(x,_)| x, |&(x,_)| x)
@@ -238,12 +1889,459 @@ mod tests {
 
     #[test]
     fn keys_in_structs() {
-        let v = vec![A{key:0, c:'a'}, A{key:1, c:'b'}, A{key:2,c:'c'}];
-        let w = vec![B{key:1, i:10}, B{key:2,i:22}, B{key:3, i:33}];
+        let v = [A{key:0, c:'a'}, A{key:1, c:'b'}, A{key:2,c:'c'}];
+        let w = [B{key:1, i:10}, B{key:2,i:22}, B{key:3, i:33}];
 
         let join_it = v.iter().join(w.iter(), |&A{key,..}| key, |&B{key,..}| key)
             .map(|(&A{c,..}, &B{i,..})| (c,i));
 
         assert_eq!( vec![('b',10),('c',22)], join_it.collect::<Vec<(char,i32)>>() );
     }
+
+    #[test]
+    fn duplicate_keys() {
+        let v = [(1,'a'), (2,'b'), (2,'c'), (3,'d')];
+        let w = [(2,20), (2,21), (2,22), (3,30)];
+
+        let join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('b',20), ('b',21), ('b',22), ('c',20), ('c',21), ('c',22), ('d',30)],
+                    join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    fn duplicate_left_keys() {
+        let v = [(1,'a'), (1,'b'), (1,'c'), (2,'d')];
+        let w = [(0,0), (1,10), (3,30)];
+
+        let join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('a',10), ('b',10), ('c',10)], join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    fn duplicate_keys_internal_iterator() {
+        let v = [(4,'a'), (5,'b'), (5,'c'), (5,'d')];
+        let w = [(5,50), (5,51), (6,60)];
+
+        let mut r = vec![];
+        join_it( v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), &(_,b)| {
+            r.push((a,b));
+        });
+
+        assert_eq!( vec![('b',50), ('b',51), ('c',50), ('c',51), ('d',50), ('d',51)], r );
+    }
+
+    #[test]
+    fn owned_non_copy_items() {
+        let v = vec![(0, "a".to_string()), (1, "b".to_string()), (2, "c".to_string())];
+        let w = vec![(1, "x".to_string()), (2, "y".to_string()), (3, "z".to_string())];
+
+        let join_it = v.join(w, |(x,_)| x, |(x,_)| x)
+            .map(|((_,a),(_,b))| a + &b);
+
+        assert_eq!( vec!["bx".to_string(), "cy".to_string()], join_it.collect::<Vec<String>>() );
+    }
+
+    struct Row {
+        key: u32,
+        name: String,
+    }
+
+    #[test]
+    fn non_clone_items_by_reference() {
+        let v = [Row{key:0, name:"a".to_string()}, Row{key:1, name:"b".to_string()}];
+        let w = [Row{key:1, name:"x".to_string()}, Row{key:2, name:"y".to_string()}];
+
+        let join_it = v.iter().join(w.iter(), |r| r.key, |r| r.key)
+            .map(|(a, b)| format!("{}{}", a.name, b.name));
+
+        assert_eq!( vec!["bx".to_string()], join_it.collect::<Vec<String>>() );
+    }
+
+    #[test]
+    fn size_hint() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,11), (2,22), (3,33), (4,44)];
+
+        let mut join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        assert_eq!( (0, Some(12)), join_it.size_hint() );
+
+        join_it.next();
+        assert_eq!( (0, Some(4)), join_it.size_hint() );
+
+        join_it.by_ref().count();
+        assert_eq!( (0, Some(0)), join_it.size_hint() );
+
+        let unbounded = (0..).join(w.iter(), |x| x, |&(x,_)| x);
+        assert_eq!( (0, None), unbounded.size_hint() );
+
+        let many_to_many = vec![1, 1, 1].into_iter().join(vec![1, 1], |x| x, |x| x);
+        assert_eq!( (0, Some(6)), many_to_many.size_hint() );
+        assert_eq!( 6, many_to_many.count() );
+    }
+
+    fn assert_fused<T: FusedIterator>(_: &T) {}
+
+    /// Drains `iter` and checks that it keeps returning `None`, for the exhaustion tests of the
+    /// iterator families of the other modules.
+    pub(crate) fn assert_exhausted<T: Iterator>(mut iter: T) {
+        iter.by_ref().for_each(drop);
+        assert!( iter.next().is_none() );
+        assert!( iter.next().is_none() );
+        assert_eq!( 0, iter.size_hint().0 );
+    }
+
+    #[test]
+    fn fused_iterators() {
+        let v = [(0,'a'), (1,'b')];
+        let w = [(1,11)];
+        let key = |&(x,_): &(u32,char)| x;
+        let key_w = |&(x,_): &(u32,u32)| x;
+
+        let mut join_it = v.iter().join(w.iter(), key, key_w);
+        assert_fused(&join_it);
+        assert_eq!( 1, join_it.by_ref().count() );
+        assert_eq!( None, join_it.next() );
+
+        assert_fused(&v.iter().join_by(w.iter(), |a, b| a.0.cmp(&b.0)));
+        assert_fused(&v.iter().left_join(w.iter(), key, key_w));
+        assert_fused(&v.iter().right_join(w.iter(), key, key_w));
+        assert_fused(&v.iter().full_join(w.iter(), key, key_w));
+        assert_fused(&v.iter().semi_join(w.iter(), key, key_w));
+        assert_fused(&v.iter().anti_join(w.iter(), key, key_w));
+    }
+
+    #[test]
+    fn joins_stay_exhausted() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (3,'d')];
+        let w = [(1,11), (1,12), (2,22), (3,33)];
+        let key = |&(x,_): &(u32,char)| x;
+        let key_w = |&(x,_): &(u32,u32)| x;
+
+        assert_exhausted(v.iter().join(w.iter(), key, key_w));
+        assert_exhausted(v.iter().join_ref(w.iter(), |&(x,_)| x, |&(x,_)| x));
+        assert_exhausted(v.iter().join(w.iter(), key, key_w).then_join(w.iter(), |(&(x,_),_)| x, key_w));
+        assert_exhausted(diff_join(w.iter(), w.iter().skip(1), key_w, key_w));
+        assert_exhausted(temporal_join(v.iter(), w.iter(), key, key_w, 1).with_strategy(WindowJoinStrategy::Nearest));
+        assert_exhausted(v.iter().join_chunks(w.iter(), key, key_w, 2));
+        #[cfg(feature = "std")]
+        assert_exhausted(hash_join(v.iter(), w.iter(), key, key_w));
+        assert_exhausted(v.iter().join_by_keys(w.iter(), &[|a: &&(u32,char), b: &&(u32,u32)| a.0.cmp(&b.0)]));
+        assert_exhausted(join_with_cmp(v.iter(), w.iter(), key, key_w, |a: &u32, b: &u32| a.cmp(b)));
+        assert_exhausted(v.iter().join_filter(w.iter(), key, key_w, |&&(_,a), _| a != 'b'));
+        assert_exhausted(v.iter().asof_join(w.iter(), key, key_w));
+        assert_exhausted(v.iter().join_within(w.iter(), key, key_w, 1));
+        assert_exhausted(v.iter().group_join(w.iter(), key, key_w));
+        assert_exhausted(v.iter().left_join_or(w.iter(), key, key_w, || &(9,99)));
+    }
+
+    #[test]
+    fn duplicate_left_keys_hold_the_right_item() {
+        let v = [1, 1, 2];
+        let w = [1, 2];
+
+        let rows = v.iter().join(w.iter(), |&x| x, |&x| x).collect::<Vec<_>>();
+        assert_eq!( vec![(&1,&1), (&1,&1), (&2,&2)], rows );
+        assert_eq!( vec![(&1,&1), (&1,&1), (&2,&2)], w.iter().join(v.iter(), |&x| x, |&x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn skipped_keys_between_matches() {
+        let v = [1, 3];
+        let w = [1, 2, 3];
+
+        let mut join_it = v.iter().join(w.iter(), |&x| x, |&x| x);
+        assert_eq!( Some((&1,&1)), join_it.next() );
+        assert_eq!( Some((&3,&3)), join_it.next() );
+        assert_eq!( None, join_it.next() );
+        assert_eq!( vec![(&1,&1), (&3,&3)], w.iter().join(v.iter(), |&x| x, |&x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn clone_mid_iteration() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d'), (3,'e')];
+        let w = [(1,10), (1,11), (2,20), (3,30)];
+
+        let mut join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        join_it.next();
+        join_it.next();
+
+        let copy = join_it.clone().collect::<Vec<_>>();
+        assert_eq!( vec![(&(1,'c'),&(1,10)), (&(1,'c'),&(1,11)), (&(2,'d'),&(2,20)), (&(3,'e'),&(3,30))], copy );
+        assert_eq!( copy, join_it.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn descending_keys() {
+        let v = [(4,'e'), (3,'d'), (2,'c'), (0,'a')];
+        let w = [(5,55), (3,33), (2,22), (1,11), (0,0)];
+
+        let join_it = v.iter().join_desc(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('d',33), ('c',22), ('a',0)], join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    fn reversed_join() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d'), (4,'e'), (4,'f')];
+        let w = [(1,10), (1,11), (2,20), (3,30), (4,40)];
+
+        let mut rows = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x).collect::<Vec<_>>();
+        rows.reverse();
+        assert_eq!( rows, v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x).rev().collect::<Vec<_>>() );
+
+        let v = [(4,'e'), (3,'d'), (2,'c'), (0,'a')];
+        let w = [(5,55), (3,33), (2,22), (1,11), (0,0)];
+        let rows = v.iter().join_desc(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .rev()
+            .map(|(&(_,a),&(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('a',0), ('c',22), ('d',33)], rows );
+    }
+
+    #[test]
+    fn front_and_back_meet() {
+        let inputs = vec![
+            (vec![1, 2, 2, 3], vec![2, 2, 3]),
+            (vec![1, 1, 1], vec![1, 1]),
+            (vec![0, 1, 1, 2, 5, 5], vec![1, 1, 2, 2, 3, 5]),
+            (vec![2, 2], vec![0, 2, 2, 4]),
+        ];
+        for (v, w) in inputs {
+            let rows = v.iter().join(w.iter(), |&x| x, |&x| x).collect::<Vec<_>>();
+            for mask in 0..1u32 << rows.len() {
+                let mut join_it = v.iter().join(w.iter(), |&x| x, |&x| x);
+                let (mut front, mut back) = (0, rows.len());
+                for n in 0..rows.len() {
+                    assert_eq!( rows.len() - n, join_it.clone().count() );
+                    if mask & 1 << n == 0 {
+                        assert_eq!( Some(rows[front]), join_it.next() );
+                        front += 1;
+                    } else {
+                        back -= 1;
+                        assert_eq!( Some(rows[back]), join_it.next_back() );
+                    }
+                }
+                assert_eq!( None, join_it.next() );
+                assert_eq!( None, join_it.next_back() );
+            }
+        }
+    }
+
+    #[test]
+    fn debug_format() {
+        let v = [(0,'a'), (1,'b')];
+        let w = [(1,11)];
+
+        let join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        let text = format!("{:?}", join_it);
+        assert!( text.starts_with("JoinIt") );
+        assert!( text.contains("<key_fn>") );
+
+        assert!( !format!("{:?}", v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x)).is_empty() );
+        assert!( !format!("{:?}", v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x)).is_empty() );
+        assert!( !format!("{:?}", v.iter().full_join(w.iter(), |&(x,_)| x, |&(x,_)| x)).is_empty() );
+        assert!( !format!("{:?}", v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x)).is_empty() );
+        assert!( !format!("{:?}", v.iter().join_by(w.iter(), |a, b| a.0.cmp(&b.0))).is_empty() );
+    }
+
+    #[test]
+    fn fold_internal_iterator() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,"x"), (2,"y"), (3,"z")];
+
+        let s = join_fold( v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, String::new(), |mut s, &(_,a), &(_,b)| {
+            s.push(a);
+            s.push_str(b);
+            s
+        });
+        assert_eq!( "bxcy", s );
+
+        let none = join_fold( v.iter(), w.iter(), |&(x,_)| x + 10, |&(x,_)| x, 42, |acc, _, _| acc + 1 );
+        assert_eq!( 42, none );
+    }
+
+    #[test]
+    fn count_rows() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d'), (4,'e')];
+        let w = [(1,10), (1,11), (1,12), (2,20), (3,30)];
+        let empty: Vec<(u32,u32)> = vec![];
+
+        assert_eq!( 7, join_count(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x) );
+        assert_eq!( 1 + 2*2 + 1 + 1, join_count(v.iter(), v.iter(), |&(x,_)| x, |&(x,_)| x) );
+        assert_eq!( 0, join_count(v.iter(), w.iter(), |&(x,_)| x + 10, |&(x,_)| x) );
+        assert_eq!( 0, join_count(v.iter(), empty.iter(), |&(x,_)| x, |&(x,_)| x) );
+        assert_eq!( 0, join_count(empty.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x) );
+
+        let mut join_it = v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        join_it.next();
+        assert_eq!( 6, join_it.clone().count() );
+        assert_eq!( 6, join_it.collect::<Vec<_>>().len() );
+    }
+
+    #[test]
+    fn seek_into_a_matched_range() {
+        let v = vec![(1,'a'), (2,'b'), (2,'c'), (3,'d'), (5,'e')];
+        let w = vec![(2,20), (2,21), (3,30), (4,40), (5,50)];
+
+        let mut rows = v.join(w, |(k,_)| k, |(k,_)| k);
+        assert_eq!( Some(((2,'b'), (2,20))), rows.next() );
+        rows.seek(2);
+        assert_eq!( Some(((2,'b'), (2,21))), rows.next() );
+        rows.seek(3);
+        assert_eq!( vec![((3,'d'), (3,30)), ((5,'e'), (5,50))], rows.clone().collect::<Vec<_>>() );
+        rows.seek(4);
+        assert_eq!( vec![((5,'e'), (5,50))], rows.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn seek_past_the_end() {
+        let mut rows = vec![1, 2, 2, 3].join(vec![2, 2, 3], |k| k, |k| k);
+        assert_eq!( Some((3, 3)), rows.next_back() );
+        rows.seek(4);
+        assert_eq!( None, rows.next() );
+        assert_eq!( None, rows.next_back() );
+    }
+
+    #[test]
+    fn count_clones_fewer_items_than_the_rows() {
+        use std::cell::Cell;
+
+        #[derive(Debug)]
+        struct Tracked<'a>(u32, &'a Cell<usize>);
+
+        impl<'a> Clone for Tracked<'a> {
+            fn clone(&self) -> Self {
+                self.1.set(self.1.get() + 1);
+                Tracked(self.0, self.1)
+            }
+        }
+
+        let clones = Cell::new(0);
+        let unique = [0, 1, 2, 4].iter().map(|&k| Tracked(k, &clones)).collect::<Vec<_>>();
+        let dups = [1, 1, 1, 2, 2, 4, 4, 4, 4].iter().map(|&k| Tracked(k, &clones)).collect::<Vec<_>>();
+
+        for w in [&unique, &dups] {
+            clones.set(0);
+            let rows = dups.clone().join(w.clone(), |t| t.0, |t| t.0).collect::<Vec<_>>().len();
+            let collected = clones.replace(0);
+            assert_eq!( rows, dups.clone().join(w.clone(), |t| t.0, |t| t.0).count() );
+            assert!( clones.get() < collected );
+        }
+    }
+
+    #[test]
+    fn any_row_stops_early() {
+        use std::cell::Cell;
+
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d')];
+        let w = [(0,0), (1,10), (2,20), (3,30)];
+        let empty: Vec<(u32,u32)> = vec![];
+
+        let pulled = Cell::new(0);
+        let left = v.iter().inspect(|_| pulled.set(pulled.get() + 1));
+        assert!( join_any(left, w.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| true) );
+        assert_eq!( 2, pulled.get() );
+
+        pulled.set(0);
+        let left = v.iter().inspect(|_| pulled.set(pulled.get() + 1));
+        assert!( !join_any(left, w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), _| a == 'e') );
+        assert_eq!( 4, pulled.get() );
+
+        assert!( join_any(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), &(_,b)| a == 'c' && b == 20) );
+        assert!( !join_any(empty.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| true) );
+        assert!( !join_any(v.iter(), empty.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| true) );
+    }
+
+    #[test]
+    fn find_row() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d')];
+        let w = [(1,10), (1,11), (2,20)];
+        let empty: Vec<(u32,u32)> = vec![];
+
+        assert_eq!( Some((&(1,'b'),&(1,10))), join_find(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| true) );
+        assert_eq!( Some((&(1,'c'),&(1,11))), join_find(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&&(_,a), &&(_,b)| a == 'c' && b == 11) );
+        assert_eq!( None, join_find(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&&(_,a), _| a == 'a') );
+        assert_eq!( None, join_find(v.iter(), empty.iter(), |&(x,_)| x, |&(x,_)| x, |_, _| true) );
+    }
+
+    #[test]
+    fn until_break() {
+        use std::cell::Cell;
+        use std::ops::ControlFlow;
+
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d'), (4,'e')];
+        let w = [(1,10), (2,20), (3,30)];
+
+        let pulled = Cell::new(0);
+        let left = v.iter().inspect(|_| pulled.set(pulled.get() + 1));
+        let res = join_it_until(left, w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), &(_,b)| {
+            if b >= 20 { ControlFlow::Break(a) } else { ControlFlow::Continue(()) }
+        });
+        assert_eq!( ControlFlow::Break('c'), res );
+        assert_eq!( 4, pulled.get() );
+
+        let mut rows = vec![];
+        let res: ControlFlow<()> = join_it_until(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a), _| {
+            rows.push(a);
+            ControlFlow::Continue(())
+        });
+        assert_eq!( ControlFlow::Continue(()), res );
+        assert_eq!( vec!['b', 'c', 'd'], rows );
+    }
+
+    #[test]
+    fn optional_skips_none_items() {
+        let v = vec![None, Some((1,'a')), None, Some((2,'b')), Some((4,'c')), None];
+        let w = vec![Some((1,10)), None, None, Some((2,20)), Some((2,21)), Some((3,30)), None, Some((4,40))];
+
+        let mut rows = vec![];
+        join_it_optional(v, w, |(k,_)| k, |(k,_)| k, |(_,a), (_,b)| rows.push((a, b)));
+        assert_eq!( vec![('a',10), ('b',20), ('b',21), ('c',40)], rows );
+    }
+
+    #[test]
+    fn join_on_shared_key() {
+        let v: Vec<(u32,char)> = vec![(0,'a'), (1,'b'), (1,'c'), (3,'d')];
+        let w: Vec<(u32,char)> = vec![(1,'x'), (2,'y'), (3,'z')];
+
+        let join_it = v.iter().join_on(w.iter(), |&(k,_)| k)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('b','x'), ('c','x'), ('d','z')], join_it.collect::<Vec<(char,char)>>() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the right sequence isn't sorted")]
+    fn unsorted_right_side() {
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d')];
+        let w = [(0,0), (2,20), (1,10), (3,30)];
+
+        v.iter().join(w.iter(), |&(x,_)| x, |&(x,_)| x).for_each(drop);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the left sequence isn't sorted")]
+    fn unsorted_left_side_internal_iterator() {
+        let v = vec![(0,'a'), (2,'c'), (1,'b')];
+        let w = vec![(0,0), (1,10), (2,20)];
+
+        join_it(v, w, |(x,_)| x, |(x,_)| x, |_, _| {});
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the left sequence isn't sorted")]
+    fn unsorted_descending_keys() {
+        let v = [(2,'c'), (0,'a'), (1,'b')];
+        let w = [(2,20), (1,10), (0,0)];
+
+        v.iter().join_desc(w.iter(), |&(x,_)| x, |&(x,_)| x).count();
+    }
 }
@@ -0,0 +1,666 @@
+//! The sorted merge driving all the join iterators.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::mem;
+use core::ops::Range;
+
+
+/// The direction in which the joined sequences are sorted by their keys.
+///
+/// Both sequences have to be sorted in the same direction. Joining an ascending sequence with a
+/// descending one doesn't fail, but quietly yields a (typically empty) partial result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+
+impl Order {
+    pub(crate) fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            Order::Ascending => ordering,
+            Order::Descending => ordering.reverse(),
+        }
+    }
+}
+
+
+/// Compares a left item with a right one to drive the merge.
+///
+/// Comparators that can also order two items of the same side return `Some` from `cmp_left` and
+/// `cmp_right`, letting debug builds check the sort order of both sides as the merge advances.
+/// `skip_left` and `skip_right` are handed every item `Merge::next_with` steps past because its key
+/// isn't reached by the other side.
+pub(crate) trait Compare<A, B> {
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering;
+
+    fn skip_left(&mut self, _v: &A) {}
+
+    fn skip_right(&mut self, _w: &B) {}
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, _v0: &A, _v1: &A) -> Option<Ordering> {
+        None
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, _w0: &B, _w1: &B) -> Option<Ordering> {
+        None
+    }
+}
+
+
+impl<A,B,F> Compare<A,B> for F where
+    F: FnMut(&A, &B) -> Ordering
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        self(v, w)
+    }
+}
+
+
+/// Compares items by the keys extracted from clones of them.
+pub(crate) struct Keys<'a, KI: 'a, KJ: 'a>(pub(crate) &'a mut KI, pub(crate) &'a mut KJ);
+
+
+impl<'a,A,B,K,KI,KJ> Compare<A,B> for Keys<'a,KI,KJ> where
+    A: Clone,
+    B: Clone,
+    KI: FnMut(A) -> K,
+    KJ: FnMut(B) -> K,
+    K: Ord
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        Ord::cmp(&(self.0)(v.clone()), &(self.1)(w.clone()))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, v0: &A, v1: &A) -> Option<Ordering> {
+        Some(Ord::cmp(&(self.0)(v0.clone()), &(self.0)(v1.clone())))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, w0: &B, w1: &B) -> Option<Ordering> {
+        Some(Ord::cmp(&(self.1)(w0.clone()), &(self.1)(w1.clone())))
+    }
+}
+
+
+/// Compares items by the keys extracted from clones of them, ordering the keys by a comparator.
+pub(crate) struct KeysBy<'a, KI: 'a, KJ: 'a, C: 'a>(pub(crate) &'a mut KI, pub(crate) &'a mut KJ, pub(crate) &'a mut C);
+
+
+impl<'a,A,B,K,KI,KJ,C> Compare<A,B> for KeysBy<'a,KI,KJ,C> where
+    A: Clone,
+    B: Clone,
+    KI: FnMut(A) -> K,
+    KJ: FnMut(B) -> K,
+    C: FnMut(&K, &K) -> Ordering
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        (self.2)(&(self.0)(v.clone()), &(self.1)(w.clone()))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, v0: &A, v1: &A) -> Option<Ordering> {
+        Some((self.2)(&(self.0)(v0.clone()), &(self.0)(v1.clone())))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, w0: &B, w1: &B) -> Option<Ordering> {
+        Some((self.2)(&(self.1)(w0.clone()), &(self.1)(w1.clone())))
+    }
+}
+
+
+/// A single step of the merge: an item without a match on the other side, or a matched pair.
+pub(crate) enum Row<A, B> {
+    Left(A),
+    Right(B),
+    Both(A, B),
+}
+
+
+/// Walks two sorted iterators in step, holding on to the current item of each side until that
+/// side's cursor actually advances.
+///
+/// Right items sharing a key are buffered in `group` and replayed against every left item with
+/// that key, so duplicate keys on both sides produce all of their pairings.
+///
+/// `keep_left` and `keep_right` decide whether unmatched items of either side are returned as
+/// `Row::Left`/`Row::Right` or silently skipped.
+///
+/// Inner joins of double ended iterators can also be merged from the back. The back buffers a
+/// whole key group of both sides at a time, pairing them by the index in `back_rows`. Once the
+/// iterators of one side run dry, each end takes the item the other end holds on to, unless that
+/// item is already being paired, in which case the rows left are all in one group and are handed
+/// to `back_rows` for both ends to take from.
+#[derive(Clone, Debug)]
+pub(crate) struct Merge<I, J> where
+    I: Iterator,
+    J: Iterator
+{
+    i: I,
+    j: J,
+    curr_i: Option<I::Item>,
+    curr_j: Option<J::Item>,
+    group: Vec<J::Item>,
+    pos: usize,
+    curr_back_i: Option<I::Item>,
+    curr_back_j: Option<J::Item>,
+    back_group_i: Vec<I::Item>,
+    back_group_j: Vec<J::Item>,
+    back_rows: Range<usize>,
+    primed: bool,
+    keep_left: bool,
+    keep_right: bool,
+    order: Order,
+}
+
+
+impl<I,J> Merge<I,J> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) fn new(i: I, j: J, keep_left: bool, keep_right: bool) -> Self {
+        Merge {
+            i,
+            j,
+            curr_i: None,
+            curr_j: None,
+            group: Vec::new(),
+            pos: 0,
+            curr_back_i: None,
+            curr_back_j: None,
+            back_group_i: Vec::new(),
+            back_group_j: Vec::new(),
+            back_rows: 0..0,
+            primed: false,
+            keep_left,
+            keep_right,
+            order: Order::Ascending,
+        }
+    }
+
+    pub(crate) fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The merged iterators, for adaptors keeping state of their own in them.
+    pub(crate) fn iters(&self) -> (&I, &J) {
+        (&self.i, &self.j)
+    }
+
+    /// The merged iterators, like `iters`.
+    pub(crate) fn iters_mut(&mut self) -> (&mut I, &mut J) {
+        (&mut self.i, &mut self.j)
+    }
+
+    /// Wraps the merged iterators with `f` and `g`, keeping the settings of the merge.
+    ///
+    /// Panics if the merge has already taken items from either end, as they would skip the wrappers.
+    pub(crate) fn map_iters<I2,J2,F,G>(self, f: F, g: G) -> Merge<I2,J2> where
+        I2: Iterator,
+        J2: Iterator,
+        F: FnOnce(I) -> I2,
+        G: FnOnce(J) -> J2
+    {
+        let untouched = !self.primed && self.curr_back_i.is_none() && self.curr_back_j.is_none()
+            && self.back_group_i.is_empty() && self.back_group_j.is_empty() && self.back_rows.is_empty();
+        assert!(untouched, "join_it: the join has already been advanced");
+
+        Merge::new(f(self.i), g(self.j), self.keep_left, self.keep_right).with_order(self.order)
+    }
+
+    /// Bounds on the number of rows still to come, counting the items held or buffered by the merge.
+    ///
+    /// Duplicate keys are joined many-to-many, so the matched pairs are only bounded by the product
+    /// of the items left on both sides rather than the smaller of the two.
+    pub(crate) fn size_hint(&self) -> (usize, Option<usize>) {
+        let ((lo_i, hi_i), (lo_j, hi_j)) = self.side_hints();
+        let hi_group = hi_j.and_then(|hi| hi.checked_add(self.group.len()));
+        let back = self.back_rows.len();
+
+        let mut lower = back;
+        let mut upper = match (hi_i, hi_group) {
+            (Some(0), _) | (_, Some(0)) => Some(0),
+            (Some(i), Some(j)) => i.checked_mul(j),
+            _ => None,
+        }.and_then(|n| n.checked_add(back));
+        if self.keep_left {
+            lower = lo_i.saturating_add(back);
+            upper = upper.and_then(|n| hi_i?.checked_add(n));
+        }
+        if self.keep_right {
+            lower = lower.max(lo_j.saturating_add(back));
+            upper = upper.and_then(|n| hi_j?.checked_add(n));
+        }
+        (lower, upper)
+    }
+
+    /// Bounds on the number of items left on each side, counting the items held by the merge but not
+    /// the buffered ones.
+    pub(crate) fn side_hints(&self) -> ((usize, Option<usize>), (usize, Option<usize>)) {
+        fn add((lo, hi): (usize, Option<usize>), n: usize) -> (usize, Option<usize>) {
+            (lo.saturating_add(n), hi.and_then(|hi| hi.checked_add(n)))
+        }
+
+        let held_i = self.curr_i.is_some() as usize + self.curr_back_i.is_some() as usize;
+        let held_j = self.curr_j.is_some() as usize + self.curr_back_j.is_some() as usize;
+        (add(self.i.size_hint(), held_i), add(self.j.size_hint(), held_j))
+    }
+
+    /// Skips the rows still pairing the left item of the last `Row::Both` with its key group,
+    /// returning the right items of those rows.
+    ///
+    /// Lets the joins taking a left item once, however many right items share its key, leave the
+    /// rest of the group unpaired.
+    pub(crate) fn skip_pairs<C>(&mut self, compare: &mut C) -> &[J::Item] where
+        C: Compare<I::Item, J::Item>
+    {
+        if self.pos == 0 {
+            return &[];
+        }
+        let pos = mem::replace(&mut self.pos, 0);
+        if let Some(v) = self.curr_i.take() {
+            self.curr_i = self.next_i(&v, compare);
+        }
+        &self.group[pos..]
+    }
+
+    /// The next left item, taking the one held by the back once the left iterator is exhausted.
+    fn pull_i(&mut self) -> Option<I::Item> {
+        self.i.next().or_else(|| self.curr_back_i.take())
+    }
+
+    /// The next right item, like `pull_i`.
+    fn pull_j(&mut self) -> Option<J::Item> {
+        self.j.next().or_else(|| self.curr_back_j.take())
+    }
+
+    /// Advances the left side past `prev`, checking in debug builds that the keys don't go
+    /// backwards if `compare` can tell.
+    fn next_i<C>(&mut self, prev: &I::Item, compare: &mut C) -> Option<I::Item> where
+        C: Compare<I::Item, J::Item>
+    {
+        let next = self.pull_i();
+        #[cfg(debug_assertions)]
+        {
+            let ordering = next.as_ref().and_then(|next| compare.cmp_left(prev, next));
+            assert!(ordering.map(|o| self.order.apply(o)) != Some(Ordering::Greater),
+                    "join_it: the left sequence isn't sorted by its key");
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = (prev, compare);
+        next
+    }
+
+    /// Advances the right side past `prev`, like `next_i`.
+    fn next_j<C>(&mut self, prev: &J::Item, compare: &mut C) -> Option<J::Item> where
+        C: Compare<I::Item, J::Item>
+    {
+        let next = self.pull_j();
+        #[cfg(debug_assertions)]
+        {
+            let ordering = next.as_ref().and_then(|next| compare.cmp_right(prev, next));
+            assert!(ordering.map(|o| self.order.apply(o)) != Some(Ordering::Greater),
+                    "join_it: the right sequence isn't sorted by its key");
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = (prev, compare);
+        next
+    }
+
+    /// Advances the merge to the next row worth returning, comparing items with `compare`.
+    pub(crate) fn next_by<C>(&mut self, mut compare: C) -> Option<Row<I::Item, J::Item>> where
+        I::Item: Clone,
+        J::Item: Clone,
+        C: FnMut(&I::Item, &J::Item) -> Ordering
+    {
+        self.next_with(&mut compare)
+    }
+
+    /// Advances the merge like `next_by`, with a comparator that may also check the sort order.
+    pub(crate) fn next_with<C>(&mut self, compare: &mut C) -> Option<Row<I::Item, J::Item>> where
+        I::Item: Clone,
+        J::Item: Clone,
+        C: Compare<I::Item, J::Item>
+    {
+        use core::cmp::Ordering::*;
+
+        let order = self.order;
+
+        if !self.primed {
+            self.curr_i = self.pull_i();
+            self.curr_j = self.pull_j();
+            self.primed = true;
+        }
+
+        loop {
+            if self.pos == 0 && !self.group.is_empty() {
+                let same_key = match self.curr_i {
+                    Some(ref v) => order.apply(compare.cmp(v, &self.group[0])) == Equal,
+                    None => false,
+                };
+                if !same_key {
+                    self.group.clear();
+                }
+            }
+
+            if let Some(w) = self.group.get(self.pos).cloned() {
+                self.pos += 1;
+                let v = if self.pos < self.group.len() {
+                    self.curr_i.clone()
+                } else {
+                    self.pos = 0;
+                    let v = self.curr_i.take();
+                    if let Some(ref v) = v {
+                        self.curr_i = self.next_i(v, compare);
+                    }
+                    v
+                };
+                return v.map(|v| Row::Both(v, w));
+            }
+
+            match (self.curr_i.take(), self.curr_j.take()) {
+                (Some(v), Some(w)) => match order.apply(compare.cmp(&v, &w)) {
+                    Less => {
+                        compare.skip_left(&v);
+                        self.curr_i = self.next_i(&v, compare);
+                        self.curr_j = Some(w);
+                        if self.keep_left {
+                            return Some(Row::Left(v));
+                        }
+                    },
+                    Greater => {
+                        compare.skip_right(&w);
+                        self.curr_i = Some(v);
+                        self.curr_j = self.next_j(&w, compare);
+                        if self.keep_right {
+                            return Some(Row::Right(w));
+                        }
+                    },
+                    Equal => {
+                        self.curr_j = self.next_j(&w, compare);
+                        self.group.push(w);
+                        while let Some(w) = self.curr_j.take() {
+                            if order.apply(compare.cmp(&v, &w)) != Equal {
+                                self.curr_j = Some(w);
+                                break;
+                            }
+                            self.curr_j = self.next_j(&w, compare);
+                            self.group.push(w);
+                        }
+                        self.curr_i = Some(v);
+                    },
+                },
+                (Some(v), None) => {
+                    if !self.keep_left {
+                        self.curr_i = Some(v);
+                        return None;
+                    }
+                    self.curr_i = self.next_i(&v, compare);
+                    return Some(Row::Left(v));
+                },
+                (None, Some(w)) => {
+                    if !self.keep_right {
+                        self.curr_j = Some(w);
+                        return None;
+                    }
+                    self.curr_j = self.next_j(&w, compare);
+                    return Some(Row::Right(w));
+                },
+                (None, None) => return None,
+            }
+        }
+    }
+
+    /// Drops the items on both sides that `left` and `right` order before the target they compare
+    /// against, along with the buffered rows of an earlier key.
+    pub(crate) fn seek_by<F,G>(&mut self, mut left: F, mut right: G) where
+        F: FnMut(&I::Item) -> Ordering,
+        G: FnMut(&J::Item) -> Ordering
+    {
+        let order = self.order;
+        let mut below_i = |v: &I::Item| order.apply(left(v)) == Ordering::Less;
+        let mut below_j = |w: &J::Item| order.apply(right(w)) == Ordering::Less;
+
+        if !self.primed {
+            self.curr_i = self.pull_i();
+            self.curr_j = self.pull_j();
+            self.primed = true;
+        }
+        if self.group.first().map_or(false, &mut below_j) {
+            self.group.clear();
+            self.pos = 0;
+        }
+        while self.curr_i.as_ref().map_or(false, &mut below_i) {
+            self.curr_i = self.pull_i();
+        }
+        while self.curr_j.as_ref().map_or(false, &mut below_j) {
+            self.curr_j = self.pull_j();
+        }
+        if self.back_group_j.first().map_or(false, &mut below_j) {
+            self.back_rows = 0..0;
+        }
+    }
+
+    /// Counts the remaining matched pairs, without cloning the buffered right items for each of
+    /// them. Unmatched items are skipped regardless of `keep_left` and `keep_right`.
+    pub(crate) fn count_by<C>(mut self, compare: &mut C) -> usize where
+        I::Item: Clone,
+        J::Item: Clone,
+        C: Compare<I::Item, J::Item>
+    {
+        use core::cmp::Ordering::*;
+
+        let mut count = 0;
+        while self.pos != 0 {
+            match self.next_with(compare) {
+                Some(Row::Both(..)) => count += 1,
+                _ => break,
+            }
+        }
+        if !self.primed {
+            self.curr_i = self.pull_i();
+            self.curr_j = self.pull_j();
+            self.primed = true;
+        }
+
+        let order = self.order;
+
+        if let Some(w) = self.group.pop() {
+            let n = self.group.len() + 1;
+            while let Some(v) = self.curr_i.take() {
+                if order.apply(compare.cmp(&v, &w)) != Equal {
+                    self.curr_i = Some(v);
+                    break;
+                }
+                count += n;
+                self.curr_i = self.next_i(&v, compare);
+            }
+        }
+
+        loop {
+            match (self.curr_i.take(), self.curr_j.take()) {
+                (Some(v), Some(w)) => match order.apply(compare.cmp(&v, &w)) {
+                    Less => {
+                        self.curr_i = self.next_i(&v, compare);
+                        self.curr_j = Some(w);
+                    },
+                    Greater => {
+                        self.curr_i = Some(v);
+                        self.curr_j = self.next_j(&w, compare);
+                    },
+                    Equal => {
+                        let mut n = 1;
+                        self.curr_j = self.next_j(&w, compare);
+                        while let Some(x) = self.curr_j.take() {
+                            if order.apply(compare.cmp(&v, &x)) != Equal {
+                                self.curr_j = Some(x);
+                                break;
+                            }
+                            n += 1;
+                            self.curr_j = self.next_j(&x, compare);
+                        }
+                        let mut v = v;
+                        loop {
+                            count += n;
+                            match self.next_i(&v, compare) {
+                                Some(next) if order.apply(compare.cmp(&next, &w)) == Equal => v = next,
+                                next => {
+                                    self.curr_i = next;
+                                    break;
+                                },
+                            }
+                        }
+                    },
+                },
+                _ => return count + self.back_rows.len(),
+            }
+        }
+    }
+
+    /// The next row of the group buffered by the back, once the front has run out of rows.
+    pub(crate) fn next_in_back_group(&mut self) -> Option<(I::Item, J::Item)> where
+        I::Item: Clone,
+        J::Item: Clone
+    {
+        let row = self.back_rows.next()?;
+        Some(self.back_row(row))
+    }
+
+    fn back_row(&self, row: usize) -> (I::Item, J::Item) where
+        I::Item: Clone,
+        J::Item: Clone
+    {
+        let n = self.back_group_j.len();
+        (self.back_group_i[row / n].clone(), self.back_group_j[row % n].clone())
+    }
+
+    /// Whether `v` shares the key of the group buffered by the front.
+    fn in_front_group<C>(&self, v: &I::Item, compare: &mut C) -> bool where
+        C: Compare<I::Item, J::Item>
+    {
+        match self.group.first() {
+            Some(w) => self.order.apply(compare.cmp(v, w)) == Ordering::Equal,
+            None => false,
+        }
+    }
+
+    /// Hands the rows the front has left of its group over to `back_rows`, along with the left
+    /// items still to come and `last`, all of which share the key of the group.
+    fn take_front_group(&mut self, last: Option<I::Item>) where
+        I::Item: Clone
+    {
+        self.back_group_i.clear();
+        self.back_group_i.extend(self.curr_i.take());
+        self.back_group_i.extend(self.i.by_ref());
+        self.back_group_i.extend(last);
+        mem::swap(&mut self.back_group_j, &mut self.group);
+        self.group.clear();
+        self.back_rows = self.pos..self.back_group_i.len() * self.back_group_j.len();
+        self.pos = 0;
+    }
+}
+
+
+impl<I,J> Merge<I,J> where
+    I: DoubleEndedIterator,
+    J: DoubleEndedIterator
+{
+    /// Advances the merge from the back to the last matched pair, like `next_with` does from the
+    /// front but buffering the items of both sides sharing a key.
+    pub(crate) fn next_back_with<C>(&mut self, compare: &mut C) -> Option<(I::Item, J::Item)> where
+        I::Item: Clone,
+        J::Item: Clone,
+        C: Compare<I::Item, J::Item>
+    {
+        use core::cmp::Ordering::*;
+
+        let order = self.order;
+
+        loop {
+            if let Some(row) = self.back_rows.next_back() {
+                return Some(self.back_row(row));
+            }
+
+            let v = match self.curr_back_i.take().or_else(|| self.i.next_back()) {
+                Some(v) => v,
+                None => match self.curr_i.take() {
+                    Some(v) => {
+                        if self.in_front_group(&v, compare) {
+                            self.curr_i = Some(v);
+                            self.take_front_group(None);
+                            continue;
+                        }
+                        v
+                    },
+                    None => return None,
+                },
+            };
+            let front_pairs_v = match self.curr_i {
+                Some(ref curr) => self.in_front_group(curr, compare) && self.in_front_group(&v, compare),
+                None => false,
+            };
+            if front_pairs_v {
+                self.take_front_group(Some(v));
+                continue;
+            }
+
+            let w = match self.curr_back_j.take().or_else(|| self.j.next_back()).or_else(|| self.curr_j.take()) {
+                Some(w) => w,
+                None => continue,
+            };
+
+            match order.apply(compare.cmp(&v, &w)) {
+                Less => self.curr_back_i = Some(v),
+                Greater => self.curr_back_j = Some(w),
+                Equal => {
+                    self.back_group_i.clear();
+                    self.back_group_j.clear();
+                    loop {
+                        match self.i.next_back() {
+                            Some(x) if order.apply(compare.cmp(&x, &w)) == Equal => self.back_group_i.push(x),
+                            Some(x) => {
+                                self.curr_back_i = Some(x);
+                                break;
+                            },
+                            None => {
+                                match self.curr_i.take() {
+                                    Some(x) if order.apply(compare.cmp(&x, &w)) == Equal => self.back_group_i.push(x),
+                                    x => self.curr_i = x,
+                                }
+                                break;
+                            },
+                        }
+                    }
+                    loop {
+                        match self.j.next_back() {
+                            Some(x) if order.apply(compare.cmp(&v, &x)) == Equal => self.back_group_j.push(x),
+                            Some(x) => {
+                                self.curr_back_j = Some(x);
+                                break;
+                            },
+                            None => {
+                                match self.curr_j.take() {
+                                    Some(x) if order.apply(compare.cmp(&v, &x)) == Equal => self.back_group_j.push(x),
+                                    x => self.curr_j = x,
+                                }
+                                break;
+                            },
+                        }
+                    }
+                    self.back_group_i.reverse();
+                    self.back_group_i.push(v);
+                    self.back_group_j.reverse();
+                    self.back_group_j.push(w);
+                    self.back_rows = 0..self.back_group_i.len() * self.back_group_j.len();
+                },
+            }
+        }
+    }
+}
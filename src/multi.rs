@@ -0,0 +1,845 @@
+//! Inner joins and merges of more than two sorted sequences.
+
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+use adaptors::JoinWithKey;
+use merge::{Keys, Merge, Row};
+use Joinable;
+
+
+/// Inner join of three sorted iterators, created by `Joinable::join3`.
+///
+/// The first two sequences are joined with their keys, the rows of which are then joined with the
+/// third sequence, so duplicate keys give every combination of the items sharing them and a key
+/// missing from any of the three sequences drops the items of the other two.
+pub struct Join3It<I, J, L, KI, KJ, KL, K> where
+    I: Iterator,
+    J: Iterator,
+    L: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Clone
+{
+    pub(crate) merge: Merge<JoinWithKey<I, J, KI, KJ, K>, L>,
+    pub(crate) kl: KL,
+}
+
+
+/// Inner join of `i`, `j` and `l`, based on the key extractors `ki`, `kj` and `kl`, like
+/// `Joinable::join3`.
+///
+/// ```
+/// use join_it::join3;
+///
+/// let users = vec![(1,"ann"),(2,"bo"),(3,"cy")];
+/// let orders = vec![(1,"book"),(3,"pen"),(3,"ink")];
+/// let payments = vec![(1,10),(2,20),(3,30)];
+///
+/// let rows = join3(users, orders, payments, |(k,_)| k, |(k,_)| k, |(k,_)| k)
+///     .map(|((_,u),(_,o),(_,p))| (u, o, p))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![("ann","book",10),("cy","pen",30),("cy","ink",30)], rows);
+/// ```
+pub fn join3<I,J,L,K,KI,KJ,KL>( i: I, j: J, l: L, ki: KI, kj: KJ, kl: KL ) -> Join3It<I::IntoIter,J::IntoIter,L::IntoIter,KI,KJ,KL,K> where
+    I: IntoIterator,
+    J: IntoIterator,
+    L: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    L::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord + Clone
+{
+    i.join3(j, l, ki, kj, kl)
+}
+
+
+/// Maps f over the join of `i`, `j` and `l`, based on the key extractors `ki`, `kj` and `kl`.
+///
+/// ```
+/// use join_it::join3_it;
+/// let u = vec![(0,'a'),(1,'b'),(2,'c')];
+/// let v = vec![(1,10),(2,20)];
+/// let w = vec![(0,"zero"),(2,"two")];
+/// join3_it(u, v, w, |(k,_)| k, |(k,_)| k, |(k,_)| k, |(k,a),(_,b),(_,c)| {
+///     assert_eq!(k, 2);
+///     println!("Join result: ({},{},{})", a, b, c);
+/// });
+/// ```
+pub fn join3_it<I,J,L,K,KI,KJ,KL,F>( i: I, j: J, l: L, ki: KI, kj: KJ, kl: KL, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    L: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    L::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    KL: Fn(L::Item) -> K,
+    F: FnMut(I::Item, J::Item, L::Item),
+    K: Ord + Clone
+{
+    for (u, v, w) in i.join3(j, l, ki, kj, kl) {
+        f(u, v, w);
+    }
+}
+
+
+impl<I,J,L,KI,KJ,KL,K> Iterator for Join3It<I,J,L,KI,KJ,KL,K> where
+    I: Iterator,
+    J: Iterator,
+    L: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    L::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord + Clone
+{
+    type Item = (I::Item, J::Item, L::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut key = |(k, _, _): (K, I::Item, J::Item)| k;
+
+        match self.merge.next_with(&mut Keys(&mut key, &mut self.kl)) {
+            Some(Row::Both((_, u, v), w)) => Some((u, v, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,L,KI,KJ,KL,K> Clone for Join3It<I,J,L,KI,KJ,KL,K> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    L: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    L::Item: Clone,
+    KI: FnMut(I::Item) -> K + Clone,
+    KJ: FnMut(J::Item) -> K + Clone,
+    KL: Clone,
+    K: Ord + Clone
+{
+    fn clone(&self) -> Self {
+        Join3It {
+            merge: self.merge.clone(),
+            kl: self.kl.clone(),
+        }
+    }
+}
+
+
+impl<I,J,L,KI,KJ,KL,K> FusedIterator for Join3It<I,J,L,KI,KJ,KL,K> where
+    I: FusedIterator,
+    J: FusedIterator,
+    L: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    L::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    KL: FnMut(L::Item) -> K,
+    K: Ord + Clone
+{}
+
+
+impl<I,J,L,KI,KJ,KL,K> fmt::Debug for Join3It<I,J,L,KI,KJ,KL,K> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    L: Iterator + fmt::Debug,
+    I::Item: Clone + fmt::Debug,
+    J::Item: Clone + fmt::Debug,
+    L::Item: fmt::Debug,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + Clone + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Join3It")
+            .field("merge", &self.merge)
+            .field("kl", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Inner join of any number of sorted iterators of the same type, created by `join_all`.
+///
+/// Yields a `Vec` holding an item of every input, in the order of the inputs, for each key found in
+/// all of them. The items sharing a key are buffered, one run per input, and every combination of
+/// them is returned before moving on to the next key.
+pub struct JoinAll<I, KF> where
+    I: Iterator
+{
+    iters: Vec<Peekable<I>>,
+    key: KF,
+    groups: Vec<Vec<I::Item>>,
+    pos: Vec<usize>,
+}
+
+
+/// Inner join of the sorted `iters`, all using the key extractor `key`.
+///
+/// Joining no iterators at all yields nothing, while joining a single one yields each of its items
+/// on its own.
+///
+/// ```
+/// use join_it::join_all;
+///
+/// let iters = vec![vec![1,2,3], vec![2,3,4], vec![0,2,3]];
+///
+/// let rows = join_all(iters, |k| k).collect::<Vec<_>>();
+/// assert_eq!(vec![vec![2,2,2], vec![3,3,3]], rows);
+/// ```
+pub fn join_all<II,KF,K>(iters: II, key: KF) -> JoinAll<<II::Item as IntoIterator>::IntoIter, KF> where
+    II: IntoIterator,
+    II::Item: IntoIterator,
+    <II::Item as IntoIterator>::Item: Clone,
+    KF: FnMut(<II::Item as IntoIterator>::Item) -> K,
+    K: Ord
+{
+    JoinAll {
+        iters: iters.into_iter().map(|iter| iter.into_iter().peekable()).collect(),
+        key,
+        groups: Vec::new(),
+        pos: Vec::new(),
+    }
+}
+
+
+impl<I,KF,K> JoinAll<I,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    /// Advances all inputs to the next key they share and buffers the items holding it, returning
+    /// `None` once any of the inputs runs out first.
+    fn fill_groups(&mut self) -> Option<()> {
+        let key = &mut self.key;
+
+        loop {
+            let mut max = None;
+            for iter in &mut self.iters {
+                let k = key(iter.peek()?.clone());
+                match max {
+                    Some(ref max) if *max >= k => {},
+                    _ => max = Some(k),
+                }
+            }
+            let max = max?;
+
+            let mut aligned = true;
+            for iter in &mut self.iters {
+                loop {
+                    let k = key(iter.peek()?.clone());
+                    if k >= max {
+                        aligned &= k == max;
+                        break;
+                    }
+                    iter.next();
+                }
+            }
+            if !aligned {
+                continue;
+            }
+
+            for iter in &mut self.iters {
+                let mut group = Vec::new();
+                while let Some(v) = iter.peek().cloned() {
+                    if key(v.clone()) != max {
+                        break;
+                    }
+                    iter.next();
+                    group.push(v);
+                }
+                self.groups.push(group);
+            }
+            self.pos.clear();
+            self.pos.resize(self.groups.len(), 0);
+            return Some(());
+        }
+    }
+}
+
+
+impl<I,KF,K> Iterator for JoinAll<I,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.groups.is_empty() {
+            self.fill_groups()?;
+        }
+
+        let row = self.groups.iter().zip(&self.pos).map(|(group, &p)| group[p].clone()).collect();
+
+        let mut carry = true;
+        for (group, p) in self.groups.iter().zip(&mut self.pos).rev() {
+            *p += 1;
+            if *p < group.len() {
+                carry = false;
+                break;
+            }
+            *p = 0;
+        }
+        if carry {
+            self.groups.clear();
+        }
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exhausted = self.iters.is_empty() || self.iters.iter().any(|iter| iter.size_hint().1 == Some(0));
+
+        if self.groups.is_empty() && exhausted {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+
+impl<I,KF> Clone for JoinAll<I,KF> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    KF: Clone
+{
+    fn clone(&self) -> Self {
+        JoinAll {
+            iters: self.iters.clone(),
+            key: self.key.clone(),
+            groups: self.groups.clone(),
+            pos: self.pos.clone(),
+        }
+    }
+}
+
+
+impl<I,KF,K> FusedIterator for JoinAll<I,KF> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,KF> fmt::Debug for JoinAll<I,KF> where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinAll")
+            .field("iters", &self.iters)
+            .field("key", &"<key_fn>")
+            .field("groups", &self.groups)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+
+/// The head item of one of the inputs of `KMerge`, ordered to put the least key on top of the heap
+/// and, among equal keys, the earliest input.
+#[derive(Clone, Debug)]
+struct Head<T, K> {
+    key: K,
+    index: usize,
+    item: T,
+}
+
+
+impl<T,K> Ord for Head<T,K> where
+    K: Ord
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&(&other.key, other.index), &(&self.key, self.index))
+    }
+}
+
+
+impl<T,K> PartialOrd for Head<T,K> where
+    K: Ord
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl<T,K> PartialEq for Head<T,K> where
+    K: Ord
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+
+impl<T,K> Eq for Head<T,K> where
+    K: Ord
+{}
+
+
+/// Merge of any number of sorted iterators of the same type, created by `kmerge`.
+///
+/// The head items of the inputs are kept in a binary heap by their keys, so each item takes
+/// O(log k) steps for k inputs. Items with equal keys are returned in the order of their inputs,
+/// and in the order they come in within an input.
+pub struct KMerge<I, KF, K> where
+    I: Iterator
+{
+    iters: Vec<I>,
+    heap: BinaryHeap<Head<I::Item, K>>,
+    key: KF,
+    primed: bool,
+}
+
+
+/// Merges the sorted `iters`, all using the key extractor `key`, into a single sorted sequence of
+/// all of their items.
+///
+/// ```
+/// use join_it::kmerge;
+///
+/// let iters = vec![vec![1,4,7], vec![2,5], vec![], vec![0,3,6,9]];
+///
+/// assert_eq!((0..8).chain(Some(9)).collect::<Vec<_>>(), kmerge(iters, |k| k).collect::<Vec<_>>());
+/// ```
+pub fn kmerge<II,KF,K>(iters: II, key: KF) -> KMerge<<II::Item as IntoIterator>::IntoIter, KF, K> where
+    II: IntoIterator,
+    II::Item: IntoIterator,
+    <II::Item as IntoIterator>::Item: Clone,
+    KF: FnMut(<II::Item as IntoIterator>::Item) -> K,
+    K: Ord
+{
+    let iters = iters.into_iter().map(IntoIterator::into_iter).collect::<Vec<_>>();
+
+    KMerge {
+        heap: BinaryHeap::with_capacity(iters.len()),
+        iters,
+        key,
+        primed: false,
+    }
+}
+
+
+impl<I,KF,K> KMerge<I,KF,K> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    /// Pushes the next item of the input at `index` on the heap, if there is one.
+    fn pull(&mut self, index: usize) {
+        if let Some(item) = self.iters[index].next() {
+            let key = (self.key)(item.clone());
+            self.heap.push(Head { key, index, item });
+        }
+    }
+
+    /// Pops the head with the least key, replacing it with the next item of its input.
+    fn pop_head(&mut self) -> Option<Head<I::Item, K>> {
+        if !self.primed {
+            for index in 0..self.iters.len() {
+                self.pull(index);
+            }
+            self.primed = true;
+        }
+
+        let head = self.heap.pop()?;
+        self.pull(head.index);
+        Some(head)
+    }
+}
+
+
+impl<I,KF,K> Iterator for KMerge<I,KF,K> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop_head().map(|head| head.item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().fold((self.heap.len(), Some(self.heap.len())), |(lo, hi), iter| {
+            let (lo_i, hi_i) = iter.size_hint();
+            (lo.saturating_add(lo_i), hi.and_then(|hi| hi.checked_add(hi_i?)))
+        })
+    }
+}
+
+
+impl<I,KF,K> Clone for KMerge<I,KF,K> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    KF: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        KMerge {
+            iters: self.iters.clone(),
+            heap: self.heap.clone(),
+            key: self.key.clone(),
+            primed: self.primed,
+        }
+    }
+}
+
+
+impl<I,KF,K> FusedIterator for KMerge<I,KF,K> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,KF,K> fmt::Debug for KMerge<I,KF,K> where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KMerge")
+            .field("iters", &self.iters)
+            .field("heap", &self.heap)
+            .field("key", &"<key_fn>")
+            .field("primed", &self.primed)
+            .finish()
+    }
+}
+
+
+/// Merge of any number of sorted iterators of the same type grouped by key, created by
+/// `k_way_merge_join`.
+///
+/// Works like `KMerge`, but yields a `Vec` of all the items sharing the least key at a time, in the
+/// order `KMerge` gives them. Unlike `JoinAll`, a key doesn't have to be found in every input.
+pub struct KWayJoinIt<I, KF, K> where
+    I: Iterator
+{
+    merge: KMerge<I, KF, K>,
+}
+
+
+/// Merges the sorted `iters`, all using the key extractor `key`, into the groups of the items that
+/// share a key.
+///
+/// ```
+/// use join_it::k_way_merge_join;
+///
+/// let shards = vec![vec![(1,'a'),(3,'b')], vec![(1,'c'),(2,'d')], vec![(3,'e')]];
+///
+/// let rows = k_way_merge_join(shards, |(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![vec![(1,'a'),(1,'c')], vec![(2,'d')], vec![(3,'b'),(3,'e')]], rows);
+/// ```
+pub fn k_way_merge_join<II,KF,K>(iters: II, key: KF) -> KWayJoinIt<<II::Item as IntoIterator>::IntoIter, KF, K> where
+    II: IntoIterator,
+    II::Item: IntoIterator,
+    <II::Item as IntoIterator>::Item: Clone,
+    KF: FnMut(<II::Item as IntoIterator>::Item) -> K,
+    K: Ord
+{
+    KWayJoinIt { merge: kmerge(iters, key) }
+}
+
+
+impl<I,KF,K> Iterator for KWayJoinIt<I,KF,K> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Head { key, item, .. } = self.merge.pop_head()?;
+        let mut row = Vec::new();
+        row.push(item);
+
+        while self.merge.heap.peek().map_or(false, |next| next.key == key) {
+            row.extend(self.merge.pop_head().map(|next| next.item));
+        }
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.merge.size_hint();
+        (lo.min(1), hi)
+    }
+}
+
+
+impl<I,KF,K> Clone for KWayJoinIt<I,KF,K> where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    KF: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        KWayJoinIt { merge: self.merge.clone() }
+    }
+}
+
+
+impl<I,KF,K> FusedIterator for KWayJoinIt<I,KF,K> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,KF,K> fmt::Debug for KWayJoinIt<I,KF,K> where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KWayJoinIt")
+            .field("merge", &self.merge)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use tests::assert_exhausted;
+
+    #[test]
+    fn join3_orders_customers_regions() {
+        let orders = [(1,"o1"), (2,"o2"), (2,"o3"), (4,"o4")];
+        let customers = [(1,"alice"), (2,"bob"), (3,"carol"), (4,"dave")];
+        let regions = [(1,"north"), (2,"south"), (4,"west")];
+
+        let rows = orders.iter().join3(customers.iter(), regions.iter(), |&(k,_)| k, |&(k,_)| k, |&(k,_)| k)
+            .map(|(&(_,o), &(_,c), &(_,r))| (o, c, r))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("o1","alice","north"), ("o2","bob","south"), ("o3","bob","south"), ("o4","dave","west")], rows );
+    }
+
+    #[test]
+    fn join3_drops_keys_missing_from_one_side() {
+        let u = vec![(0,'a'), (1,'b'), (2,'c'), (3,'d')];
+        let v = vec![(0,10), (1,11), (3,13)];
+        let w = vec![(1,"one"), (2,"two"), (3,"three")];
+
+        assert_eq!( vec![1, 3], u.clone().join3(v.clone(), w.clone(), |(k,_)| k, |(k,_)| k, |(k,_)| k)
+                        .map(|((k,_),_,_)| k).collect::<Vec<_>>() );
+        assert_eq!( vec![1, 3], w.join3(u, v, |(k,_)| k, |(k,_)| k, |(k,_)| k)
+                        .map(|((k,_),_,_)| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join3_gap_in_each_sequence() {
+        let full = vec![1, 2, 3, 4];
+        let gap = vec![1, 3, 4];
+
+        assert_eq!( vec![1, 3, 4], join3(gap.clone(), full.clone(), full.clone(), |k| k, |k| k, |k| k).map(|(k,_,_)| k).collect::<Vec<_>>() );
+        assert_eq!( vec![1, 3, 4], join3(full.clone(), gap.clone(), full.clone(), |k| k, |k| k, |k| k).map(|(k,_,_)| k).collect::<Vec<_>>() );
+        assert_eq!( vec![1, 3, 4], join3(full.clone(), full, gap, |k| k, |k| k, |k| k).map(|(k,_,_)| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join3_duplicate_keys() {
+        let u = vec![(1,'a'), (1,'b')];
+        let v = vec![(1,10), (1,11)];
+        let w = vec![(0,0), (1,100), (1,101), (2,200)];
+
+        let rows = u.join3(v, w, |(k,_)| k, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( 8, rows.len() );
+        assert_eq!( ((1,'a'), (1,10), (1,100)), rows[0] );
+        assert_eq!( ((1,'b'), (1,11), (1,101)), rows[7] );
+    }
+
+    #[test]
+    fn join3_with_an_empty_side() {
+        let u = vec![0, 1, 2];
+        let empty: Vec<i32> = vec![];
+
+        let mut join_it = u.clone().join3(empty, u, |k| k, |k| k, |k| k);
+        assert_eq!( (0, Some(0)), join_it.size_hint() );
+        assert_eq!( None, join_it.next() );
+    }
+
+    #[test]
+    fn join3_keys_missing_from_one_input() {
+        let u = vec![1, 2, 2, 3];
+        let v = vec![2, 2, 3];
+        let w = vec![1, 2, 4];
+
+        let rows = u.join3(v, w, |k| k, |k| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( vec![(2, 2, 2); 4], rows );
+    }
+
+    #[test]
+    fn join3_it_inner_iteration() {
+        let u = vec![1, 2, 3];
+        let v = vec![2, 3, 4];
+        let w = vec![3, 4, 5];
+        let mut rows = vec![];
+
+        join3_it(u, v, w, |k| k, |k| k, |k| k, |a, b, c| rows.push((a, b, c)));
+        assert_eq!( vec![(3, 3, 3)], rows );
+    }
+
+    #[test]
+    fn join_all_four_inputs() {
+        let iters = vec![
+            vec![(1,'a'), (2,'b'), (3,'c'), (5,'d')],
+            vec![(0,'e'), (2,'f'), (5,'g')],
+            vec![(2,'h'), (4,'i'), (5,'j'), (6,'k')],
+            vec![(1,'l'), (2,'m'), (3,'n'), (5,'o')],
+        ];
+
+        let rows = join_all(iters, |(k,_)| k)
+            .map(|row| row.into_iter().map(|(_,c)| c).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!( vec!["bfhm".to_string(), "dgjo".to_string()], rows );
+    }
+
+    #[test]
+    fn join_all_duplicate_keys() {
+        let iters = vec![vec![(1,'a'), (1,'b')], vec![(1,'c')], vec![(0,'x'), (1,'d'), (1,'e')]];
+
+        let rows = join_all(iters, |(k,_)| k)
+            .map(|row| row.into_iter().map(|(_,c)| c).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!( vec!["acd", "ace", "bcd", "bce"], rows );
+    }
+
+    #[test]
+    fn join_all_no_inputs() {
+        let iters: Vec<Vec<i32>> = vec![];
+
+        let mut join_it = join_all(iters, |k| k);
+        assert_eq!( (0, Some(0)), join_it.size_hint() );
+        assert_eq!( None, join_it.next() );
+    }
+
+    #[test]
+    fn join_all_single_input() {
+        let iters = vec![vec![1, 2, 2, 3]];
+
+        assert_eq!( vec![vec![1], vec![2], vec![2], vec![3]], join_all(iters, |k| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_all_input_running_out_while_advancing() {
+        let iters = vec![vec![1, 5], vec![1, 2, 3], vec![1, 6]];
+
+        assert_eq!( vec![vec![1, 1, 1]], join_all(iters, |k| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn join_all_with_an_empty_input() {
+        let iters = vec![vec![1, 2], vec![], vec![1, 2]];
+
+        assert_eq!( 0, join_all(iters, |k| k).count() );
+    }
+
+    #[test]
+    fn join_all_of_two_inputs_is_a_join() {
+        let v = vec![0, 1, 1, 3, 4];
+        let w = vec![1, 1, 2, 4];
+
+        let rows = join_all(vec![v.clone(), w.clone()], |k| k).collect::<Vec<_>>();
+        let join = v.join(w, |k| k, |k| k).map(|(a, b)| vec![a, b]).collect::<Vec<_>>();
+        assert_eq!( join, rows );
+    }
+
+    #[test]
+    fn kmerge_inputs_of_differing_lengths() {
+        let iters = vec![vec![5], vec![0, 2, 4, 6, 8, 10], vec![1, 3], vec![7, 9]];
+
+        let merge = kmerge(iters, |k| k);
+        assert_eq!( (11, Some(11)), merge.size_hint() );
+        assert_eq!( (0..11).collect::<Vec<_>>(), merge.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn kmerge_no_inputs() {
+        let iters: Vec<Vec<u32>> = vec![];
+
+        assert_eq!( 0, kmerge(iters, |k| k).count() );
+    }
+
+    #[test]
+    fn kmerge_equal_keys_are_stable() {
+        let iters = vec![
+            vec![(1,'a'), (1,'b'), (2,'c')],
+            vec![(0,'d'), (1,'e'), (2,'f')],
+            vec![(1,'g'), (1,'h')],
+        ];
+
+        let merged = kmerge(iters, |(k,_)| k).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "dabeghcf", merged );
+    }
+
+    #[test]
+    fn kmerge_empty_inputs() {
+        let iters = vec![vec![], vec![1, 3], vec![], vec![2]];
+
+        let mut merge = kmerge(iters, |k| k);
+        assert_eq!( (3, Some(3)), merge.size_hint() );
+        assert_eq!( Some(1), merge.next() );
+        assert_eq!( (2, Some(2)), merge.size_hint() );
+        assert_eq!( vec![2, 3], merge.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn k_way_merge_join_groups_shards() {
+        let shards = (0..10u32).map(|s| (0..20u32).filter(|k| k % (s + 2) == 0).collect::<Vec<_>>()).collect::<Vec<_>>();
+
+        let rows = k_way_merge_join(shards.clone(), |k| k).collect::<Vec<_>>();
+        assert_eq!( kmerge(shards, |k| k).collect::<Vec<_>>(), rows.concat() );
+        assert!( rows.iter().all(|row| row.iter().all(|&k| k == row[0])) );
+        assert_eq!( 10, rows[0].len() );
+        assert_eq!( (0..20).filter(|k| (2..12).any(|d| k % d == 0)).count(), rows.len() );
+    }
+
+    #[test]
+    fn k_way_merge_join_empty_shards() {
+        let rows = k_way_merge_join(vec![vec![], vec![2, 2], vec![], vec![2, 3]], |k| k);
+        assert_eq!( vec![vec![2, 2, 2], vec![3]], rows.collect::<Vec<_>>() );
+
+        let shards: Vec<Vec<u32>> = vec![vec![], vec![]];
+        assert_eq!( 0, k_way_merge_join(shards, |k| k).count() );
+    }
+
+    #[test]
+    fn multi_way_joins_stay_exhausted() {
+        let iters = vec![vec![1, 3, 3], vec![1, 3], vec![1, 2, 3]];
+
+        assert_exhausted(iters[0].iter().join3(iters[1].iter(), iters[2].iter(), |&k| k, |&k| k, |&k| k));
+        assert_exhausted(join_all(iters.clone(), |k| k));
+        assert_exhausted(kmerge(iters.clone(), |k| k));
+        assert_exhausted(k_way_merge_join(iters, |k| k));
+    }
+}
@@ -0,0 +1,953 @@
+//! Outer joins, keeping the rows of one side even when the other side has no matching key.
+
+use alloc::vec::{self, Vec};
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Keys, Merge, Row};
+use Joinable;
+
+
+/// Left outer join between two sorted iterators, created by `Joinable::left_join`.
+///
+/// Every item of the left iterator is returned exactly once, paired with the matching right item
+/// or `None` if the right iterator has no item with an equal key.
+pub struct LeftJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for LeftJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, Option<J::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut Keys(ki, kj))? {
+            Row::Left(v) => Some((v, None)),
+            Row::Both(v, w) => Some((v, Some(w))),
+            Row::Right(_) => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for LeftJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        LeftJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for LeftJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for LeftJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LeftJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Left outer join filling in a default right item, created by `Joinable::left_join_or`.
+///
+/// Works like `LeftJoinIt`, calling `default` for every left item without a match instead of
+/// returning `None`.
+pub struct LeftJoinOr<I, J, KI, KJ, D> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) join: LeftJoinIt<I, J, KI, KJ>,
+    pub(crate) default: D,
+}
+
+
+impl<I,J,KI,KJ,K,D> Iterator for LeftJoinOr<I,J,KI,KJ,D> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord,
+    D: FnMut() -> J::Item
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (v, w) = self.join.next()?;
+
+        Some((v, w.unwrap_or_else(&mut self.default)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ,D> Clone for LeftJoinOr<I,J,KI,KJ,D> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    D: Clone
+{
+    fn clone(&self) -> Self {
+        LeftJoinOr {
+            join: self.join.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,D> FusedIterator for LeftJoinOr<I,J,KI,KJ,D> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord,
+    D: FnMut() -> J::Item
+{}
+
+
+impl<I,J,KI,KJ,D> fmt::Debug for LeftJoinOr<I,J,KI,KJ,D> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LeftJoinOr")
+            .field("join", &self.join)
+            .field("default", &"<default_fn>")
+            .finish()
+    }
+}
+
+
+/// The rows of the inner join and the left items without a match, returned by
+/// `Joinable::partition_join`.
+pub type Partition<A, B> = (Vec<(A, B)>, Vec<A>);
+
+
+/// Right outer join between two sorted iterators, created by `Joinable::right_join`.
+///
+/// Every item of the right iterator is returned exactly once, paired with the matching left item
+/// or `None` if the left iterator has no item with an equal key.
+pub struct RightJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for RightJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (Option<I::Item>, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut Keys(ki, kj))? {
+            Row::Right(w) => Some((None, w)),
+            Row::Both(v, w) => Some((Some(v), w)),
+            Row::Left(_) => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for RightJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        RightJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for RightJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for RightJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RightJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Maps f over the full outer join between `i` and `j`, based on the key extractors `ki` and `kj`.
+///
+/// ```
+/// use join_it::full_join_it;
+/// let v = vec![(0,'a'),(1,'b')];
+/// let w = vec![(1,11),(2,22)];
+/// full_join_it(v, w, |(k,_)| k, |(k,_)| k, |a,b| {
+///     println!("Join result: ({:?},{:?})", a, b);
+/// });
+/// ```
+pub fn full_join_it<I,J,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(Option<I::Item>, Option<J::Item>),
+    K: Ord
+{
+    for (v, w) in i.full_join(j, ki, kj) {
+        f(v, w);
+    }
+}
+
+
+/// The matched rows, the left items and the right items without a match, returned by
+/// `split_join`.
+pub type SplitJoin<A, B> = (vec::IntoIter<(A, B)>, vec::IntoIter<A>, vec::IntoIter<B>);
+
+
+/// Runs the full outer join between `i` and `j`, based on the key extractors `ki` and `kj`, and
+/// splits its rows into the matched pairs, the left items without a match and the right items
+/// without a match.
+///
+/// Both sequences are consumed eagerly, buffering the three parts before they're returned.
+///
+/// ```
+/// use join_it::split_join;
+/// let v = vec![(0,'a'),(1,'b')];
+/// let w = vec![(1,11),(2,22)];
+/// let (both, left, right) = split_join(v, w, |(k,_)| k, |(k,_)| k);
+/// assert_eq!(vec![((1,'b'),(1,11))], both.collect::<Vec<_>>());
+/// assert_eq!(vec![(0,'a')], left.collect::<Vec<_>>());
+/// assert_eq!(vec![(2,22)], right.collect::<Vec<_>>());
+/// ```
+pub fn split_join<I,J,K,KI,KJ>( i: I, j: J, ki: KI, kj: KJ ) -> SplitJoin<I::Item,J::Item> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    let mut both = Vec::new();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for row in i.full_join(j, ki, kj) {
+        match row {
+            (Some(v), Some(w)) => both.push((v, w)),
+            (Some(v), None) => left.push(v),
+            (None, Some(w)) => right.push(w),
+            (None, None) => unreachable!(),
+        }
+    }
+    (both.into_iter(), left.into_iter(), right.into_iter())
+}
+
+
+/// Full outer join between two sorted iterators, created by `Joinable::full_join`.
+///
+/// Every item of both iterators is returned exactly once, in ascending key order. Items with a
+/// matching key on the other side are paired up, the others come with `None` in the other slot.
+pub struct FullJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for FullJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (Option<I::Item>, Option<J::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut Keys(ki, kj))? {
+            Row::Left(v) => Some((Some(v), None)),
+            Row::Right(w) => Some((None, Some(w))),
+            Row::Both(v, w) => Some((Some(v), Some(w))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for FullJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        FullJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for FullJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for FullJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FullJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// A row of `DiffJoinIt`, telling on which sides of the diff its key is found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JoinDiff<L, R> {
+    /// The key is only found on the right side.
+    Added(R),
+    /// The key is only found on the left side.
+    Removed(L),
+    /// The key is found on both sides, which may still differ in anything but their keys.
+    Unchanged(L, R),
+}
+
+
+/// Diff between two sorted iterators, created by `diff_join`.
+///
+/// The rows of a full outer join, labelled by the sides their items come from.
+pub struct DiffJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    join: FullJoinIt<I, J, KI, KJ>,
+}
+
+
+/// Diffs the sorted sequences `i`, the old one, and `j`, the new one, based on the key extractors
+/// `ki` and `kj`.
+///
+/// ```
+/// use join_it::{diff_join, JoinDiff};
+/// let cache = vec![(0,"a"),(1,"b")];
+/// let remote = vec![(1,"B"),(2,"c")];
+/// let diff = diff_join(cache, remote, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![JoinDiff::Removed((0,"a")), JoinDiff::Unchanged((1,"b"), (1,"B")), JoinDiff::Added((2,"c"))], diff);
+/// ```
+pub fn diff_join<I,J,K,KI,KJ>( i: I, j: J, ki: KI, kj: KJ ) -> DiffJoinIt<I::IntoIter,J::IntoIter,KI,KJ> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    DiffJoinIt { join: i.full_join(j, ki, kj) }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for DiffJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = JoinDiff<I::Item, J::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.join.next()? {
+            (Some(v), Some(w)) => Some(JoinDiff::Unchanged(v, w)),
+            (Some(v), None) => Some(JoinDiff::Removed(v)),
+            (None, Some(w)) => Some(JoinDiff::Added(w)),
+            (None, None) => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for DiffJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        DiffJoinIt { join: self.join.clone() }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for DiffJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for DiffJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DiffJoinIt")
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+
+/// Group join between two sorted iterators, created by `Joinable::group_join`.
+///
+/// Every item of the left iterator is returned exactly once, along with all of the right items
+/// sharing its key, collected into a `Vec` that is empty if there are none. The right items of a
+/// key are buffered once and handed out as a clone to each left item with that key, the buffer
+/// being reused for the next key.
+pub struct GroupJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for GroupJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, Vec<J::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut keys = Keys(&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut keys)? {
+            Row::Left(v) => Some((v, Vec::new())),
+            Row::Both(v, w) => {
+                let mut group = Vec::new();
+                group.push(w);
+                group.extend_from_slice(self.merge.skip_pairs(&mut keys));
+                Some((v, group))
+            },
+            Row::Right(_) => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.side_hints().0
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for GroupJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        GroupJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for GroupJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for GroupJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GroupJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn left_join() {
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d')];
+        let w = [(1,11), (3,33)];
+
+        let join_it = v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),b)| (a, b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![('a',None), ('b',Some(11)), ('c',None), ('d',Some(33))],
+                    join_it.collect::<Vec<(char,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn left_join_or_fills_unmatched_rows() {
+        let v = vec![0, 1, 2, 3];
+        let w = vec![(1,"one"), (3,"three")];
+
+        let mut calls = 0;
+        let rows = v.left_join_or(w, |k| k, |(k,_)| k, || { calls += 1; (0,"none") })
+            .map(|(k,(_,b))| (k, b))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(0,"none"), (1,"one"), (2,"none"), (3,"three")], rows );
+        assert_eq!( 2, calls );
+    }
+
+    #[test]
+    fn left_join_or_empty_right_and_duplicates() {
+        let rows = vec![1, 2].left_join_or(Vec::<i32>::new(), |k| k, |k| k, i32::default)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(1, 0), (2, 0)], rows );
+
+        let rows = vec![1, 2, 3].left_join_or(vec![2, 2], |k| k, |k| k, i32::default)
+            .collect::<Vec<_>>();
+        assert_eq!( vec![(1, 0), (2, 2), (2, 2), (3, 0)], rows );
+    }
+
+    #[test]
+    fn partition_join_half_matching() {
+        let v = vec![(0,'a'), (1,'b'), (2,'c'), (3,'d'), (4,'e'), (5,'f')];
+        let w = vec![(1,10), (1,11), (3,30), (5,50), (6,60)];
+
+        let (matched, unmatched) = v.clone().partition_join(w.clone(), |(k,_)| k, |(k,_)| k);
+        assert_eq!( v.join(w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>(), matched );
+        assert_eq!( vec![(0,'a'), (2,'c'), (4,'e')], unmatched );
+    }
+
+    #[test]
+    fn split_join_routes_every_row() {
+        let v = vec![0, 1, 1, 3, 5];
+        let w = vec![1, 2, 3, 3, 4];
+
+        let (both, left, right) = split_join(v.clone(), w.clone(), |k| k, |k| k);
+        assert_eq!( v.join(w, |k| k, |k| k).collect::<Vec<_>>(), both.collect::<Vec<_>>() );
+        assert_eq!( vec![0, 5], left.collect::<Vec<_>>() );
+        assert_eq!( vec![2, 4], right.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn left_join_trailing_rows() {
+        let v = [(1,'b'), (3,'d'), (4,'e'), (5,'f')];
+        let w = [(0,0), (1,11), (2,22), (3,33)];
+
+        let join_it = v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),b)| (a, b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![('b',Some(11)), ('d',Some(33)), ('e',None), ('f',None)],
+                    join_it.collect::<Vec<(char,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn left_join_exhausted_together() {
+        let v = [(0,'a'), (2,'c')];
+        let w = [(1,11), (2,22)];
+
+        let join_it = v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),b)| (a, b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![('a',None), ('c',Some(22))], join_it.collect::<Vec<(char,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn left_join_disjoint_keys() {
+        let v = [(1,'b'), (3,'d'), (5,'f')];
+        let w = [(0,0), (2,22), (4,44), (6,66)];
+
+        let join_it = v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),b)| (a, b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![('b',None), ('d',None), ('f',None)], join_it.collect::<Vec<(char,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn left_join_empty_sides() {
+        let v: Vec<(u32,char)> = vec![(1,'b')];
+        let w: Vec<(u32,u32)> = vec![];
+
+        assert_eq!( vec![(&(1,'b'), None)],
+                    v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x).collect::<Vec<_>>() );
+        assert_eq!( 0, w.iter().left_join(v.iter(), |&(x,_)| x, |&(x,_)| x).count() );
+    }
+
+    #[test]
+    fn right_join() {
+        let v = [(1,'b'), (3,'d'), (4,'e')];
+        let w = [(0,0), (1,11), (2,22), (3,33)];
+
+        let join_it = v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,&(_,b))| (a.map(|&(_,a)| a), b));
+
+        assert_eq!( vec![(None,0), (Some('b'),11), (None,22), (Some('d'),33)],
+                    join_it.collect::<Vec<(Option<char>,u32)>>() );
+    }
+
+    #[test]
+    fn right_join_trailing_rows() {
+        let v = [(0,'a'), (2,'c')];
+        let w = [(2,22), (3,33), (4,44)];
+
+        let join_it = v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,&(_,b))| (a.map(|&(_,a)| a), b));
+
+        assert_eq!( vec![(Some('c'),22), (None,33), (None,44)],
+                    join_it.collect::<Vec<(Option<char>,u32)>>() );
+    }
+
+    #[test]
+    fn right_join_exhausted_together() {
+        let v = [(1,'b'), (2,'c')];
+        let w = [(0,0), (2,22)];
+
+        let join_it = v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,&(_,b))| (a.map(|&(_,a)| a), b));
+
+        assert_eq!( vec![(None,0), (Some('c'),22)], join_it.collect::<Vec<(Option<char>,u32)>>() );
+    }
+
+    #[test]
+    fn right_join_disjoint_keys() {
+        let v = [(0,'a'), (2,'c'), (4,'e'), (6,'g')];
+        let w = [(1,11), (3,33), (5,55)];
+
+        let join_it = v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,&(_,b))| (a.map(|&(_,a)| a), b));
+
+        assert_eq!( vec![(None,11), (None,33), (None,55)], join_it.collect::<Vec<(Option<char>,u32)>>() );
+    }
+
+    #[test]
+    fn right_join_empty_sides() {
+        let v: Vec<(u32,char)> = vec![];
+        let w: Vec<(u32,u32)> = vec![(1,11)];
+
+        assert_eq!( vec![(None, &(1,11))],
+                    v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x).collect::<Vec<_>>() );
+        assert_eq!( 0, w.iter().right_join(v.iter(), |&(x,_)| x, |&(x,_)| x).count() );
+    }
+
+    #[test]
+    fn full_join() {
+        let v = [(0,'a'), (1,'b'), (3,'d'), (5,'f'), (6,'g')];
+        let w = [(1,11), (2,22), (3,33)];
+
+        let join_it = v.iter().full_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,b)| (a.map(|&(_,a)| a), b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![(Some('a'),None), (Some('b'),Some(11)), (None,Some(22)), (Some('d'),Some(33)),
+                         (Some('f'),None), (Some('g'),None)],
+                    join_it.collect::<Vec<(Option<char>,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn full_join_disjoint_keys() {
+        let v = [(1,'b'), (4,'e')];
+        let w = [(0,0), (2,22), (3,33), (5,55)];
+
+        let join_it = w.iter().full_join(v.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(b,a)| (b.map(|&(_,b)| b), a.map(|&(_,a)| a)));
+
+        assert_eq!( vec![(Some(0),None), (None,Some('b')), (Some(22),None), (Some(33),None),
+                         (None,Some('e')), (Some(55),None)],
+                    join_it.collect::<Vec<(Option<u32>,Option<char>)>>() );
+    }
+
+    #[test]
+    fn full_join_internal_iterator() {
+        let v = [(0,'a'), (2,'c')];
+        let w = [(1,11), (2,22), (3,33)];
+
+        let mut r = vec![];
+        full_join_it(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |a, b| {
+            r.push((a.map(|&(_,a)| a), b.map(|&(_,b)| b)));
+        });
+
+        assert_eq!( vec![(Some('a'),None), (None,Some(11)), (Some('c'),Some(22)), (None,Some(33))], r );
+    }
+
+    #[test]
+    fn full_join_duplicate_keys() {
+        let v = [(1,'a'), (1,'b'), (2,'c')];
+        let w = [(0,0), (1,10), (1,11), (3,30)];
+
+        let join_it = v.iter().full_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,b)| (a.map(|&(_,a)| a), b.map(|&(_,b)| b)));
+
+        assert_eq!( vec![(None,Some(0)), (Some('a'),Some(10)), (Some('a'),Some(11)), (Some('b'),Some(10)),
+                         (Some('b'),Some(11)), (Some('c'),None), (None,Some(30))],
+                    join_it.collect::<Vec<(Option<char>,Option<u32>)>>() );
+    }
+
+    #[test]
+    fn outer_joins_non_copy_items() {
+        let v = vec![(0, "a".to_string()), (1, "b".to_string())];
+        let w = vec![(1, vec![1u8]), (2, vec![2u8])];
+
+        let left = v.clone().left_join(w.clone(), |(x,_)| x, |(x,_)| x)
+            .map(|((_,a),b)| (a, b.map(|(_,b)| b)));
+        assert_eq!( vec![("a".to_string(), None), ("b".to_string(), Some(vec![1u8]))],
+                    left.collect::<Vec<_>>() );
+
+        let full = v.full_join(w, |(x,_)| x, |(x,_)| x).count();
+        assert_eq!( 3, full );
+    }
+
+    #[test]
+    fn outer_join_size_hints() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,11), (3,33)];
+
+        assert_eq!( (3, Some(3*2 + 3)), v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x).size_hint() );
+        assert_eq!( (2, Some(3*2 + 2)), v.iter().right_join(w.iter(), |&(x,_)| x, |&(x,_)| x).size_hint() );
+        assert_eq!( (3, Some(3*2 + 3 + 2)), v.iter().full_join(w.iter(), |&(x,_)| x, |&(x,_)| x).size_hint() );
+
+        let mut left = v.iter().left_join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        left.by_ref().count();
+        assert_eq!( (0, Some(0)), left.size_hint() );
+    }
+
+    #[test]
+    fn outer_join_clone_mid_iteration() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (3,'d')];
+        let w = [(1,11), (1,12), (2,22)];
+
+        let mut full = v.iter().full_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(a,b)| (a.map(|&(_,a)| a), b.map(|&(_,b)| b)));
+        full.next();
+        full.next();
+
+        let mut copy = full.clone();
+        assert_eq!( Some((Some('b'),Some(12))), copy.next() );
+        assert_eq!( vec![(Some('b'),Some(12)), (Some('c'),Some(11)), (Some('c'),Some(12)), (None,Some(22)), (Some('d'),None)],
+                    full.collect::<Vec<_>>() );
+        assert_eq!( 4, copy.count() );
+    }
+
+    #[test]
+    fn group_join() {
+        let customers = [(1,"ann"), (2,"bo"), (3,"cy"), (5,"di")];
+        let orders = [(0,"o0"), (1,"o1"), (3,"o2"), (3,"o3"), (3,"o4"), (4,"o5")];
+
+        let rows = customers.iter().group_join(orders.iter(), |&(k,_)| k, |&(k,_)| k)
+            .map(|(&(_,c), os)| (c, os.into_iter().map(|&(_,o)| o).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![("ann", vec!["o1"]), ("bo", vec![]), ("cy", vec!["o2", "o3", "o4"]), ("di", vec![])], rows );
+    }
+
+    #[test]
+    fn group_join_duplicate_left_keys() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c'), (2,'d')];
+        let w = vec![(1,10), (1,11), (3,30)];
+
+        let rows = v.group_join(w, |(k,_)| k, |(k,_)| k)
+            .map(|((_,a), bs)| (a, bs.len()))
+            .collect::<Vec<_>>();
+        assert_eq!( vec![('a',2), ('b',2), ('c',0), ('d',0)], rows );
+    }
+
+    #[test]
+    fn group_join_empty_sides() {
+        let groups = vec![1, 2].group_join(Vec::<u32>::new(), |k| k, |k| k);
+        assert_eq!( (2, Some(2)), groups.size_hint() );
+        assert_eq!( vec![(1, vec![]), (2, vec![])], groups.collect::<Vec<_>>() );
+
+        let groups = Vec::<u32>::new().group_join(vec![1, 2], |k| k, |k| k);
+        assert_eq!( 0, groups.count() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the left sequence isn't sorted")]
+    fn group_join_unsorted_left_side() {
+        [1, 3, 2].group_join([1, 2, 3], |k| k, |k| k).count();
+    }
+
+    #[test]
+    fn diff_join_labels_the_rows() {
+        let old = vec![(0,'a'), (1,'b'), (3,'d'), (4,'e')];
+        let new = vec![(1,'b'), (2,'c'), (4,'E'), (5,'f')];
+
+        let diff = diff_join(old, new, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![
+            JoinDiff::Removed((0,'a')),
+            JoinDiff::Unchanged((1,'b'), (1,'b')),
+            JoinDiff::Added((2,'c')),
+            JoinDiff::Removed((3,'d')),
+            JoinDiff::Unchanged((4,'e'), (4,'E')),
+            JoinDiff::Added((5,'f')),
+        ], diff );
+    }
+
+    #[test]
+    fn diff_join_empty_sides() {
+        let diff = diff_join(Vec::<u32>::new(), vec![1, 2], |k| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( vec![JoinDiff::Added(1), JoinDiff::Added(2)], diff );
+
+        let diff = diff_join(vec![1, 1], Vec::<u32>::new(), |k| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( vec![JoinDiff::Removed(1), JoinDiff::Removed(1)], diff );
+    }
+
+}
@@ -0,0 +1,203 @@
+//! Parallel joins, partitioning the sequences by key and joining the partitions on rayon's pool.
+
+use alloc::vec::Vec;
+use core::cmp::Ord;
+use core::ops::Range;
+use rayon::prelude::*;
+use Joinable;
+
+
+/// Splits `v` into about `parts` ranges of consecutive items, never splitting a run of items
+/// sharing a key.
+fn partition<T,K,KF>(v: &[T], parts: usize, key: KF) -> Vec<(usize, usize)> where
+    T: Clone,
+    KF: Fn(T) -> K,
+    K: Ord
+{
+    let parts = parts.max(1);
+    let len = (v.len() + parts - 1) / parts;
+    let mut ranges = Vec::with_capacity(parts);
+    let mut start = 0;
+
+    while start < v.len() {
+        let mut end = (start + len.max(1)).min(v.len());
+        let last = key(v[end - 1].clone());
+        while end < v.len() && key(v[end].clone()) == last {
+            end += 1;
+        }
+        ranges.push((start, end));
+        start = end;
+    }
+    ranges
+}
+
+
+/// Partitions `v` with `partition`, pairing each range with the range of `w` holding the same keys.
+fn key_ranges<A,B,K,KI,KJ>(v: &[A], w: &[B], ki: &KI, kj: &KJ) -> Vec<(Range<usize>, Range<usize>)> where
+    A: Clone,
+    B: Clone,
+    KI: Fn(A) -> K,
+    KJ: Fn(B) -> K,
+    K: Ord
+{
+    if v.is_empty() || w.is_empty() {
+        return Vec::new();
+    }
+
+    partition(v, rayon::current_num_threads(), ki)
+        .into_iter()
+        .map(|(start, end)| {
+            let (first, last) = (ki(v[start].clone()), ki(v[end - 1].clone()));
+            let lower = w.partition_point(|x| kj(x.clone()) < first);
+            let upper = w.partition_point(|x| kj(x.clone()) <= last);
+            (start..end, lower..upper)
+        })
+        .collect()
+}
+
+
+/// Calls f for each row of the join between `i` and `j` like `join_it`, joining parts of the
+/// sequences in parallel on rayon's thread pool.
+///
+/// Both sequences are collected first. The left one is cut into a partition per thread at key
+/// boundaries, and the right items of each partition's key range are looked up by binary search,
+/// so every row is still joined exactly once. The rows of a partition are passed to f in key order,
+/// but partitions are joined concurrently and f sees their rows interleaved.
+///
+/// ```
+/// use join_it::par_join_it;
+/// use std::sync::Mutex;
+///
+/// let v = (0..1000).map(|k| (k, k * 2)).collect::<Vec<_>>();
+/// let w = (0..1000).filter(|k| k % 3 == 0).collect::<Vec<_>>();
+/// let rows = Mutex::new(vec![]);
+/// par_join_it(v, w, |(k,_)| k, |k| k, |(_,a), _| rows.lock().unwrap().push(a));
+///
+/// let mut rows = rows.into_inner().unwrap();
+/// rows.sort();
+/// assert_eq!((0..1000).filter(|k| k % 3 == 0).map(|k| k * 2).collect::<Vec<_>>(), rows);
+/// ```
+pub fn par_join_it<I,J,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone + Send + Sync,
+    J::Item: Clone + Send + Sync,
+    KI: Fn(I::Item) -> K + Sync,
+    KJ: Fn(J::Item) -> K + Sync,
+    F: Fn(I::Item, J::Item) + Sync,
+    K: Ord
+{
+    let v = i.into_iter().collect::<Vec<_>>();
+    let w = j.into_iter().collect::<Vec<_>>();
+
+    key_ranges(&v, &w, &ki, &kj).into_par_iter().for_each(|(vs, ws)| {
+        for (a, b) in v[vs].iter().cloned().join(w[ws].iter().cloned(), &ki, &kj) {
+            f(a, b);
+        }
+    });
+}
+
+
+/// Joins the sorted slices `v` and `w` like `join`, joining parts of them in parallel on rayon's
+/// thread pool and collecting the rows.
+///
+/// The slices are partitioned like in `par_join_it`, and the rows of the partitions are
+/// concatenated in order, so they come out just like the rows of the sequential join.
+///
+/// ```
+/// use join_it::par_join;
+///
+/// let v = (0..1000).map(|k| (k, k * 2)).collect::<Vec<_>>();
+/// let w = (0..1000).filter(|k| k % 3 == 0).collect::<Vec<_>>();
+///
+/// let rows = par_join(&v, &w, |(k,_)| k, |k| k);
+/// assert_eq!(334, rows.len());
+/// assert_eq!(((999,1998),999), rows[333]);
+/// ```
+pub fn par_join<A,B,K,KI,KJ>( v: &[A], w: &[B], ki: KI, kj: KJ ) -> Vec<(A, B)> where
+    A: Clone + Send + Sync,
+    B: Clone + Send + Sync,
+    KI: Fn(A) -> K + Sync,
+    KJ: Fn(B) -> K + Sync,
+    K: Ord
+{
+    key_ranges(v, w, &ki, &kj).into_par_iter()
+        .flat_map_iter(|(vs, ws)| v[vs].iter().cloned().join(w[ws].iter().cloned(), &ki, &kj))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use std::sync::Mutex;
+
+    fn sorted_rows<T: Ord>(rows: Mutex<Vec<T>>) -> Vec<T> {
+        let mut rows = rows.into_inner().unwrap();
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn par_join_matches_join() {
+        let v = (0..5000u32).map(|k| (k / 3, k)).collect::<Vec<_>>();
+        let w = (0..4000u32).map(|k| (k / 2 + 100, k)).collect::<Vec<_>>();
+
+        let rows = Mutex::new(vec![]);
+        par_join_it(v.iter(), w.iter(), |&(k,_)| k, |&(k,_)| k, |&(_,a), &(_,b)| rows.lock().unwrap().push((a, b)));
+
+        let mut expected = v.iter().join(w.iter(), |&(k,_)| k, |&(k,_)| k)
+            .map(|(&(_,a), &(_,b))| (a, b))
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!( expected, sorted_rows(rows) );
+    }
+
+    #[test]
+    fn par_join_runs_of_a_single_key() {
+        let v = vec![1; 300];
+        let w = vec![0, 1, 1, 2];
+
+        let rows = Mutex::new(vec![]);
+        par_join_it(v, w, |k| k, |k| k, |a, b| rows.lock().unwrap().push((a, b)));
+        assert_eq!( vec![(1, 1); 600], sorted_rows(rows) );
+    }
+
+    #[test]
+    fn par_join_empty_and_disjoint() {
+        let empty: Vec<u32> = vec![];
+        let rows = Mutex::new(vec![]);
+
+        par_join_it(empty.clone(), vec![1, 2], |k| k, |k| k, |a, b| rows.lock().unwrap().push((a, b)));
+        par_join_it(vec![1, 2], empty, |k| k, |k| k, |a, b| rows.lock().unwrap().push((a, b)));
+        par_join_it(vec![1, 2], vec![3, 4], |k| k, |k| k, |a, b| rows.lock().unwrap().push((a, b)));
+        assert_eq!( Vec::<(u32, u32)>::new(), sorted_rows(rows) );
+    }
+
+    #[test]
+    fn par_join_matches_join_over_100k_rows() {
+        let v = (0..100_000u32).map(|k| (k / 4, k)).collect::<Vec<_>>();
+        let w = (0..100_000u32).map(|k| (k / 3 + 1000, k)).collect::<Vec<_>>();
+
+        let expected = v.iter().cloned().join(w.iter().cloned(), |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        assert!( expected.len() > 100_000 );
+        assert_eq!( expected, par_join(&v, &w, |(k,_)| k, |(k,_)| k) );
+    }
+
+    #[test]
+    fn par_join_empty_slices() {
+        let empty: Vec<u32> = vec![];
+
+        assert_eq!( Vec::<(u32, u32)>::new(), par_join(&empty, &[1, 2], |k| k, |k| k) );
+        assert_eq!( Vec::<(u32, u32)>::new(), par_join(&[1, 2], &empty, |k| k, |k| k) );
+        assert_eq!( vec![(2, 2)], par_join(&[1, 2], &[2, 3], |k| k, |k| k) );
+    }
+
+    #[test]
+    fn partition_keeps_key_runs_together() {
+        let v = vec![0, 0, 1, 1, 1, 1, 2, 3, 3, 4];
+
+        let ranges = super::partition(&v, 4, |k| k);
+        assert_eq!( vec![(0, 6), (6, 9), (9, 10)], ranges );
+    }
+}
@@ -0,0 +1,154 @@
+//! Joins that can be replayed from where they started.
+
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+use JoinIt;
+
+
+/// Inner join that can be restarted, created by `JoinIt::rejoinable`.
+///
+/// Keeps a copy of the join as it was when it was made rejoinable, cloning it whenever the join is
+/// restarted rather than building it again from the inputs and the key extractors.
+pub struct Rejoin<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    start: JoinIt<I, J, KI, KJ>,
+    join: JoinIt<I, J, KI, KJ>,
+}
+
+
+impl<I,J,KI,KJ> JoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    /// Makes this join restartable from its current state, typically before it has been advanced.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+    /// let w = vec![(1,11),(2,22)];
+    ///
+    /// let mut rows = v.join(w, |(k,_)| k, |(k,_)| k).rejoinable();
+    /// assert_eq!(2, rows.by_ref().count());
+    ///
+    /// rows.reset();
+    /// assert_eq!(Some(((1,'b'),(1,11))), rows.next());
+    /// assert_eq!(2, rows.restart().count());
+    /// ```
+    pub fn rejoinable(self) -> Rejoin<I,J,KI,KJ> {
+        Rejoin { start: self.clone(), join: self }
+    }
+}
+
+
+impl<I,J,KI,KJ> Rejoin<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    /// A fresh join over the same inputs, starting over from where `rejoinable` was called.
+    pub fn restart(&self) -> JoinIt<I,J,KI,KJ> {
+        self.start.clone()
+    }
+
+    /// Starts this join over, like `restart`.
+    pub fn reset(&mut self) {
+        self.join = self.start.clone();
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for Rejoin<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.join.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for Rejoin<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        Rejoin {
+            start: self.start.clone(),
+            join: self.join.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for Rejoin<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for Rejoin<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rejoin")
+            .field("start", &self.start)
+            .field("join", &self.join)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn restart_reproduces_the_rows() {
+        let v = vec![(0,'a'), (1,'b'), (1,'c'), (3,'d')];
+        let w = vec![(1,10), (1,11), (2,20), (3,30)];
+        let expected = v.clone().join(w.clone(), |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+
+        let mut rows = v.join(w, |(k,_)| k, |(k,_)| k).rejoinable();
+        assert_eq!( expected, rows.by_ref().collect::<Vec<_>>() );
+        assert_eq!( None, rows.next() );
+        assert_eq!( expected, rows.restart().collect::<Vec<_>>() );
+
+        rows.next();
+        rows.reset();
+        assert_eq!( expected, rows.collect::<Vec<_>>() );
+    }
+}
@@ -0,0 +1,425 @@
+//! Semi and anti joins, filtering the left side by the keys present on the right.
+
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Keys, Merge, Order, Row};
+use Joinable;
+
+
+/// Semi join between two sorted iterators, created by `Joinable::semi_join`.
+///
+/// Returns the items of the left iterator whose key appears in the right iterator, each of them
+/// once no matter how many right items share its key.
+pub struct SemiJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ> SemiJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Joins sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.merge = self.merge.with_order(order);
+        self
+    }
+}
+
+
+/// Maps f over the items of `i` whose key, as extracted by `ki`, matches a key of `j`.
+///
+/// ```
+/// use join_it::semi_join_it;
+/// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+/// let w = vec![(1,11),(1,12)];
+/// semi_join_it(v, w, |(k,_)| k, |(k,_)| k, |(k,a)| {
+///     assert_eq!(k, 1);
+///     println!("Matched: {}", a);
+/// });
+/// ```
+pub fn semi_join_it<I,J,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(I::Item),
+    K: Ord
+{
+    for v in i.semi_join(j, ki, kj) {
+        f(v);
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for SemiJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut keys = Keys(&mut self.ki, &mut self.kj);
+
+        match self.merge.next_with(&mut keys)? {
+            Row::Both(v, _) => {
+                self.merge.skip_pairs(&mut keys);
+                Some(v)
+            },
+            Row::Left(_) | Row::Right(_) => unreachable!(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let ((_, hi), _) = self.merge.side_hints();
+        (0, hi)
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for SemiJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        SemiJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for SemiJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for SemiJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SemiJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// Anti join between two sorted iterators, created by `Joinable::anti_join`.
+///
+/// Returns the items of the left iterator whose key doesn't appear in the right iterator.
+pub struct AntiJoinIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) merge: Merge<I, J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+}
+
+
+impl<I,J,KI,KJ> AntiJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Joins sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.merge = self.merge.with_order(order);
+        self
+    }
+}
+
+
+/// Maps f over the items of `i` whose key, as extracted by `ki`, doesn't match any key of `j`.
+///
+/// ```
+/// use join_it::anti_join_it;
+/// let v = vec![(0,'a'),(1,'b'),(2,'c')];
+/// let w = vec![(1,11)];
+/// anti_join_it(v, w, |(k,_)| k, |(k,_)| k, |(k,a)| {
+///     assert!(k != 1);
+///     println!("Unmatched: {}", a);
+/// });
+/// ```
+pub fn anti_join_it<I,J,K,KI,KJ,F>( i: I, j: J, ki: KI, kj: KJ, mut f: F ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(I::Item),
+    K: Ord
+{
+    for v in i.anti_join(j, ki, kj) {
+        f(v);
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for AntiJoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut keys = Keys(&mut self.ki, &mut self.kj);
+
+        loop {
+            match self.merge.next_with(&mut keys)? {
+                Row::Left(v) => return Some(v),
+                Row::Both(..) => {
+                    self.merge.skip_pairs(&mut keys);
+                },
+                Row::Right(_) => unreachable!(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let ((lo, hi), (_, hi_j)) = self.merge.side_hints();
+        if hi_j == Some(0) {
+            (lo, hi)
+        } else {
+            (0, hi)
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for AntiJoinIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        AntiJoinIt {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for AntiJoinIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for AntiJoinIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AntiJoinIt")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn anti_join() {
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d'), (5,'f')];
+        let w = [(1,11), (2,22), (4,44)];
+
+        let join_it = v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['a', 'd', 'f'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn anti_join_disjoint_keys() {
+        let v = [(1,'b'), (3,'d'), (5,'f')];
+        let w = [(0,0), (2,22), (4,44)];
+
+        let join_it = v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['b', 'd', 'f'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn anti_join_internal_iterator() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(0,0), (2,22), (3,33)];
+
+        let mut r = vec![];
+        anti_join_it(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a)| r.push(a));
+
+        assert_eq!( vec!['b'], r );
+    }
+
+    #[test]
+    fn anti_join_outside_right_range() {
+        let v = [(0,'a'), (1,'b'), (3,'d'), (4,'e'), (8,'i'), (9,'j')];
+        let w = [(3,33), (4,44), (4,45), (5,55)];
+
+        let join_it = v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['a', 'b', 'i', 'j'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn anti_join_duplicate_left_keys() {
+        let v = [(1,'a'), (1,'b'), (2,'c'), (2,'d')];
+        let w = [(1,11)];
+
+        let join_it = v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['c', 'd'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn semi_join() {
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d'), (5,'f')];
+        let w = [(1,11), (2,22), (4,44), (5,55)];
+
+        let join_it = v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['b', 'c', 'f'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn semi_join_duplicate_left_keys() {
+        let v = [(1,'a'), (1,'b'), (2,'c'), (3,'d'), (3,'e')];
+        let w = [(1,11), (3,33)];
+
+        let join_it = v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['a', 'b', 'd', 'e'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn semi_join_internal_iterator() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(0,0), (2,22), (3,33)];
+
+        let mut r = vec![];
+        semi_join_it(v.iter(), w.iter(), |&(x,_)| x, |&(x,_)| x, |&(_,a)| r.push(a));
+
+        assert_eq!( vec!['a', 'c'], r );
+    }
+
+    #[test]
+    fn semi_join_duplicate_right_keys() {
+        let v = [(1,'a'), (2,'b'), (3,'c')];
+        let w = [(1,10), (1,11), (1,12), (3,30), (3,31)];
+
+        let join_it = v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|&(_,a)| a);
+
+        assert_eq!( vec!['a', 'c'], join_it.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    fn semi_and_anti_join_non_copy_items() {
+        let v = vec![(0, "a".to_string()), (1, "b".to_string()), (2, "c".to_string())];
+        let w = vec![(1, "x".to_string())];
+
+        assert_eq!( vec![(1, "b".to_string())], v.clone().semi_join(w.clone(), |(x,_)| x, |(x,_)| x).collect::<Vec<_>>() );
+        assert_eq!( vec![(0, "a".to_string()), (2, "c".to_string())],
+                    v.anti_join(w, |(x,_)| x, |(x,_)| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn semi_and_anti_join_size_hints() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,11)];
+
+        assert_eq!( (0, Some(3)), v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x).size_hint() );
+        assert_eq!( (0, Some(3)), v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x).size_hint() );
+    }
+
+    #[test]
+    fn semi_and_anti_join_clone_mid_iteration() {
+        let v = [(0,'a'), (1,'b'), (2,'c'), (3,'d'), (4,'e')];
+        let w = [(1,11), (3,33)];
+
+        let mut semi = v.iter().semi_join(w.iter(), |&(x,_)| x, |&(x,_)| x).map(|&(_,a)| a);
+        semi.next();
+        let copy = semi.clone();
+        assert_eq!( vec!['d'], semi.collect::<Vec<char>>() );
+        assert_eq!( vec!['d'], copy.collect::<Vec<char>>() );
+
+        let mut anti = v.iter().anti_join(w.iter(), |&(x,_)| x, |&(x,_)| x).map(|&(_,a)| a);
+        anti.next();
+        let mut copy = anti.clone();
+        assert_eq!( Some('c'), copy.next() );
+        assert_eq!( vec!['c', 'e'], anti.collect::<Vec<char>>() );
+        assert_eq!( vec!['e'], copy.collect::<Vec<char>>() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the right sequence isn't sorted")]
+    fn semi_join_unsorted_right_side() {
+        [1, 2, 3].semi_join([3, 1], |k| k, |k| k).count();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the left sequence isn't sorted")]
+    fn anti_join_unsorted_left_side() {
+        [1, 3, 2].anti_join([2], |k| k, |k| k).count();
+    }
+}
@@ -0,0 +1,817 @@
+//! Set operations on sorted sequences.
+
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+use merge::Order;
+use semi::{AntiJoinIt, SemiJoinIt};
+use sorted::Side;
+
+
+/// The key extractor of the set operations.
+pub(crate) type Identity<T> = fn(T) -> T;
+
+
+pub(crate) fn identity<T>(v: T) -> T {
+    v
+}
+
+
+/// Intersection of two sorted sequences, created by `Joinable::intersect`.
+///
+/// A semi join with the items as keys, so every item of the left sequence that is also in the right
+/// one is returned once, however many times the right sequence holds it.
+pub type Intersect<I, J> = SemiJoinIt<I, J, Identity<<I as Iterator>::Item>, Identity<<J as Iterator>::Item>>;
+
+
+/// Difference of two sorted sequences, created by `Joinable::difference`.
+///
+/// An anti join with the items as keys, so every item of the left sequence that isn't in the right
+/// one is returned, duplicates included.
+pub type Difference<I, J> = AntiJoinIt<I, J, Identity<<I as Iterator>::Item>, Identity<<J as Iterator>::Item>>;
+
+
+/// Union of two sorted sequences, created by `Joinable::union`.
+///
+/// Items with a key found on one side only are converted into the output with `Into`, while a
+/// left and a right item sharing a key are merged into a single output by a closure. Duplicate
+/// keys are paired up one by one, so a key held twice on the left and three times on the right
+/// gives two merged items followed by a right one.
+pub struct UnionIt<I, J, KI, KJ, F> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: Peekable<I>,
+    pub(crate) j: Peekable<J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) f: F,
+    pub(crate) order: Order,
+}
+
+
+impl<I,J,KI,KJ,F> UnionIt<I,J,KI,KJ,F> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Merges sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+
+impl<I,J,KI,KJ,K,F,C> Iterator for UnionIt<I,J,KI,KJ,F> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone + Into<C>,
+    J::Item: Clone + Into<C>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> C,
+    K: Ord
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use core::cmp::Ordering::*;
+
+        let ordering = match (self.i.peek(), self.j.peek()) {
+            (Some(v), Some(w)) => self.order.apply(Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(w.clone()))),
+            (Some(_), None) => Less,
+            (None, Some(_)) => Greater,
+            (None, None) => return None,
+        };
+        match ordering {
+            Less => self.i.next().map(Into::into),
+            Greater => self.j.next().map(Into::into),
+            Equal => {
+                let (v, w) = (self.i.next()?, self.j.next()?);
+                Some((self.f)(v, w))
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo_i, hi_i) = self.i.size_hint();
+        let (lo_j, hi_j) = self.j.size_hint();
+        let upper = match (hi_i, hi_j) {
+            (Some(i), Some(j)) => i.checked_add(j),
+            _ => None,
+        };
+        (lo_i.max(lo_j), upper)
+    }
+}
+
+
+impl<I,J,KI,KJ,F> Clone for UnionIt<I,J,KI,KJ,F> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone,
+    F: Clone
+{
+    fn clone(&self) -> Self {
+        UnionIt {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            f: self.f.clone(),
+            order: self.order,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K,F,C> FusedIterator for UnionIt<I,J,KI,KJ,F> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone + Into<C>,
+    J::Item: Clone + Into<C>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    F: FnMut(I::Item, J::Item) -> C,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,F> fmt::Debug for UnionIt<I,J,KI,KJ,F> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UnionIt")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("f", &"<map_fn>")
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+
+/// Symmetric difference of two sorted sequences, created by `Joinable::sym_difference`.
+///
+/// Yields the items with a key found on one side only, in key order, converting the right ones
+/// into the type of the left ones with `Into`. A left and a right item sharing a key are skipped
+/// together, so like `UnionIt` duplicate keys are paired up one by one and only the surplus of
+/// either side is returned.
+pub struct SymDiffIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: Peekable<I>,
+    pub(crate) j: Peekable<J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) order: Order,
+}
+
+
+impl<I,J,KI,KJ> SymDiffIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Merges sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for SymDiffIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone + Into<I::Item>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use core::cmp::Ordering::*;
+
+        loop {
+            let ordering = match (self.i.peek(), self.j.peek()) {
+                (Some(v), Some(w)) => self.order.apply(Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(w.clone()))),
+                (Some(_), None) => Less,
+                (None, Some(_)) => Greater,
+                (None, None) => return None,
+            };
+            match ordering {
+                Less => return self.i.next(),
+                Greater => return self.j.next().map(Into::into),
+                Equal => {
+                    self.i.next();
+                    self.j.next();
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, hi_i) = self.i.size_hint();
+        let (_, hi_j) = self.j.size_hint();
+        let upper = match (hi_i, hi_j) {
+            (Some(i), Some(j)) => i.checked_add(j),
+            _ => None,
+        };
+        (0, upper)
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for SymDiffIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        SymDiffIt {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            order: self.order,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for SymDiffIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone + Into<I::Item>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for SymDiffIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SymDiffIt")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+
+/// Merge of two sorted sequences into one, created by `Joinable::merge_union`.
+///
+/// Every item of both sequences is returned in key order, the right ones converted into the type
+/// of the left ones with `Into`. Ties are broken in favour of the left sequence: all left items
+/// with a key come before the right ones with the same key. With `dedup` set, each left item
+/// instead replaces a right item sharing its key, pairing duplicate keys up one by one like
+/// `UnionIt`.
+pub struct MergeUnionIt<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: Peekable<I>,
+    pub(crate) j: Peekable<J>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) dedup: bool,
+    pub(crate) order: Order,
+}
+
+
+impl<I,J,KI,KJ> MergeUnionIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Merges sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for MergeUnionIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone + Into<I::Item>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use core::cmp::Ordering::*;
+
+        let ordering = match (self.i.peek(), self.j.peek()) {
+            (Some(v), Some(w)) => self.order.apply(Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(w.clone()))),
+            (Some(_), None) => Less,
+            (None, Some(_)) => Greater,
+            (None, None) => return None,
+        };
+        match ordering {
+            Less => self.i.next(),
+            Greater => self.j.next().map(Into::into),
+            Equal => {
+                if self.dedup {
+                    self.j.next();
+                }
+                self.i.next()
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo_i, hi_i) = self.i.size_hint();
+        let (lo_j, hi_j) = self.j.size_hint();
+        let upper = match (hi_i, hi_j) {
+            (Some(i), Some(j)) => i.checked_add(j),
+            _ => None,
+        };
+        if self.dedup {
+            (lo_i.max(lo_j), upper)
+        } else {
+            (lo_i.saturating_add(lo_j), upper)
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for MergeUnionIt<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        MergeUnionIt {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            dedup: self.dedup,
+            order: self.order,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for MergeUnionIt<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone + Into<I::Item>,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for MergeUnionIt<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MergeUnionIt")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("dedup", &self.dedup)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+
+/// Intersection of two sorted sequences by key, created by `Joinable::merge_intersection`.
+///
+/// Returns the items of the left sequence whose key is also in the right one, like a semi join.
+/// Every left item with a matching key is returned, duplicates included, however many right items
+/// hold that key.
+pub type MergeIntersectionIt<I, J, KI, KJ> = SemiJoinIt<I, J, KI, KJ>;
+
+
+/// Difference of two sorted sequences by key, created by `Joinable::merge_difference`.
+///
+/// Returns the items of the left sequence whose key isn't in the right one, like an anti join,
+/// duplicates included. Once the right sequence runs out, the rest of the left one passes through.
+pub type MergeDifferenceIt<I, J, KI, KJ> = AntiJoinIt<I, J, KI, KJ>;
+
+
+/// Symmetric difference of two sorted sequences of the same items, created by
+/// `Joinable::merge_symmetric_difference`.
+///
+/// Like `SymDiffIt`, but tagging every item with the `Side` it comes from, so diffing two sorted
+/// snapshots tells the removed rows, on the left, from the added ones, on the right.
+pub struct MergeSymDiffIt<I, J, KF> where
+    I: Iterator,
+    J: Iterator
+{
+    pub(crate) i: Peekable<I>,
+    pub(crate) j: Peekable<J>,
+    pub(crate) key: KF,
+    pub(crate) order: Order,
+}
+
+
+impl<I,J,KF> MergeSymDiffIt<I,J,KF> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Merges sequences sorted in `order` by their keys, rather than ascending.
+    pub fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+
+impl<I,J,KF,K> Iterator for MergeSymDiffIt<I,J,KF> where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{
+    type Item = (Side, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use core::cmp::Ordering::*;
+
+        loop {
+            let ordering = match (self.i.peek(), self.j.peek()) {
+                (Some(v), Some(w)) => self.order.apply(Ord::cmp(&(self.key)(v.clone()), &(self.key)(w.clone()))),
+                (Some(_), None) => Less,
+                (None, Some(_)) => Greater,
+                (None, None) => return None,
+            };
+            match ordering {
+                Less => return self.i.next().map(|v| (Side::Left, v)),
+                Greater => return self.j.next().map(|w| (Side::Right, w)),
+                Equal => {
+                    self.i.next();
+                    self.j.next();
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, hi_i) = self.i.size_hint();
+        let (_, hi_j) = self.j.size_hint();
+        let upper = match (hi_i, hi_j) {
+            (Some(i), Some(j)) => i.checked_add(j),
+            _ => None,
+        };
+        (0, upper)
+    }
+}
+
+
+impl<I,J,KF> Clone for MergeSymDiffIt<I,J,KF> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KF: Clone
+{
+    fn clone(&self) -> Self {
+        MergeSymDiffIt {
+            i: self.i.clone(),
+            j: self.j.clone(),
+            key: self.key.clone(),
+            order: self.order,
+        }
+    }
+}
+
+
+impl<I,J,KF,K> FusedIterator for MergeSymDiffIt<I,J,KF> where
+    I: FusedIterator,
+    J: FusedIterator<Item = I::Item>,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KF> fmt::Debug for MergeSymDiffIt<I,J,KF> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MergeSymDiffIt")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("key", &"<key_fn>")
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn intersect() {
+        let v = vec![1, 3, 4, 6, 9];
+        let w = vec![0, 3, 6, 7, 9, 10];
+
+        assert_eq!( vec![3, 6, 9], v.intersect(w).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn intersect_identical_and_empty_sets() {
+        let v = vec!["a", "b", "c"];
+        let empty: Vec<&str> = vec![];
+
+        assert_eq!( v.clone(), v.clone().intersect(v.clone()).collect::<Vec<_>>() );
+        assert_eq!( empty.clone(), empty.clone().intersect(v.clone()).collect::<Vec<_>>() );
+        assert_eq!( empty.clone(), v.intersect(empty).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn intersect_by_reference() {
+        let v = ["x".to_string(), "y".to_string()];
+        let w = ["y".to_string(), "z".to_string()];
+
+        assert_eq!( vec![&"y".to_string()], v.iter().intersect(w.iter()).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn difference() {
+        let v = vec![1, 3, 4, 6, 9];
+        let w = vec![0, 3, 6, 7, 10];
+
+        assert_eq!( vec![1, 4, 9], v.clone().difference(w.clone()).collect::<Vec<_>>() );
+        assert_eq!( vec![0, 7, 10], w.difference(v).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn difference_keeps_duplicates() {
+        let v = vec![1, 1, 2, 2, 3];
+        let w = vec![2, 2, 2];
+
+        assert_eq!( vec![1, 1, 3], v.difference(w).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn difference_complements_intersect() {
+        let v = vec!["a", "b", "c", "d"];
+        let w = vec!["b", "d", "e"];
+
+        let mut both = v.clone().intersect(w.clone()).chain(v.clone().difference(w)).collect::<Vec<_>>();
+        both.sort();
+        assert_eq!( v, both );
+    }
+
+    #[test]
+    fn union() {
+        let v = vec![1, 3, 4, 6];
+        let w = vec![0, 3, 5, 6, 9];
+
+        assert_eq!( vec![0, 1, 3, 4, 5, 6, 9], v.union(w, |x| x, |x| x, |x, _| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn union_merges_items_with_equal_keys() {
+        let v = vec![(1,10), (2,20), (4,40)];
+        let w = vec![(2,2), (3,3), (4,4)];
+
+        let union = v.union(w, |(k,_)| k, |(k,_)| k, |(k,a), (_,b)| (k, a + b));
+        assert_eq!( vec![(1,10), (2,22), (3,3), (4,44)], union.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn union_pairs_up_duplicate_keys() {
+        let v = vec![(1,'a'), (1,'b'), (2,'c')];
+        let w = vec![(1,'x'), (2,'y'), (2,'z'), (3,'w')];
+
+        let union = v.union(w, |(k,_)| k, |(k,_)| k, |(k,a), (_,b)| (k, a.max(b)));
+        assert_eq!( vec![(1,'x'), (1,'b'), (2,'y'), (2,'z'), (3,'w')], union.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn union_with_empty_sides() {
+        let v = vec![1, 2];
+        let empty: Vec<i32> = vec![];
+
+        assert_eq!( v.clone(), v.clone().union(empty.clone(), |x| x, |x| x, |x, _| x).collect::<Vec<_>>() );
+        assert_eq!( v.clone(), empty.clone().union(v.clone(), |x| x, |x| x, |x, _| x).collect::<Vec<_>>() );
+        assert_eq!( 0, empty.clone().union(empty, |x| x, |x| x, |x, _| x).count() );
+    }
+
+    #[test]
+    fn union_size_hint() {
+        let v = vec![1, 2, 3];
+        let w = vec![2, 3, 4, 5];
+
+        let union = v.union(w, |x| x, |x| x, |x, _| x);
+        assert_eq!( (4, Some(7)), union.size_hint() );
+        assert_eq!( 5, union.count() );
+    }
+
+    #[test]
+    fn sym_difference() {
+        let v = vec![1, 3, 4, 6, 9];
+        let w = vec![0, 3, 6, 7, 10];
+
+        assert_eq!( vec![0, 1, 4, 7, 9, 10], v.sym_difference(w, |x| x, |x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn sym_difference_converts_right_items() {
+        let v = vec![1u64, 2, 5];
+        let w = vec![2u32, 3, 4];
+
+        assert_eq!( vec![1u64, 3, 4, 5], v.sym_difference(w, |x| x, u64::from).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn sym_difference_pairs_up_duplicate_keys() {
+        let v = vec![1, 1, 2];
+        let w = vec![1, 2, 2, 2];
+
+        assert_eq!( vec![1, 2, 2], v.sym_difference(w, |x| x, |x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn sym_difference_is_both_differences() {
+        let v = vec!["a", "b", "d", "f"];
+        let w = vec!["b", "c", "d", "e", "g"];
+
+        let mut both = v.clone().difference(w.clone()).chain(w.clone().difference(v.clone())).collect::<Vec<_>>();
+        both.sort();
+        assert_eq!( both, v.sym_difference(w, |x| x, |x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn intersect_and_sym_difference_make_the_union() {
+        let sets = (0..64u32)
+            .map(|bits| (0..6).filter(|i| bits & 1 << i != 0).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        for v in &sets {
+            for w in &sets {
+                let mut parts = v.iter().intersect(w.iter())
+                    .chain(v.iter().sym_difference(w.iter(), |&x| x, |&x| x))
+                    .collect::<Vec<_>>();
+                parts.sort();
+                let union = v.iter().union(w.iter(), |&x| x, |&x| x, |x, _| x).collect::<Vec<_>>();
+                assert_eq!( union, parts );
+            }
+        }
+    }
+
+    #[test]
+    fn merge_union_interleaved_keys() {
+        let v = vec![(1,'a'), (3,'b'), (3,'c'), (6,'d')];
+        let w = vec![(0,'w'), (3,'x'), (4,'y'), (6,'z')];
+
+        let merged = v.merge_union(w, |(k,_)| k, |(k,_)| k, false).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "wabcxydz", merged );
+    }
+
+    #[test]
+    fn merge_union_dedup() {
+        let v = vec![(1,'a'), (3,'b'), (3,'c'), (6,'d')];
+        let w = vec![(0,'w'), (3,'x'), (4,'y'), (6,'z')];
+
+        let merged = v.merge_union(w, |(k,_)| k, |(k,_)| k, true).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "wabcyd", merged );
+    }
+
+    #[test]
+    fn merge_union_is_a_stable_sort() {
+        let v = vec![(0,'a'), (2,'b'), (2,'c'), (5,'d')];
+        let w = vec![(1,'e'), (2,'f'), (5,'g'), (7,'h')];
+
+        let mut sorted = v.iter().chain(w.iter()).cloned().collect::<Vec<_>>();
+        sorted.sort_by_key(|&(k,_)| k);
+        assert_eq!( sorted, v.merge_union(w, |(k,_)| k, |(k,_)| k, false).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn merge_union_size_hint() {
+        let v = vec![1, 2, 3];
+        let w = vec![2, 3, 4, 5];
+
+        assert_eq!( (7, Some(7)), v.clone().merge_union(w.clone(), |x| x, |x| x, false).size_hint() );
+        assert_eq!( (4, Some(7)), v.clone().merge_union(w.clone(), |x| x, |x| x, true).size_hint() );
+        assert_eq!( 5, v.merge_union(w, |x| x, |x| x, true).count() );
+    }
+
+    #[test]
+    fn merge_intersection_overlapping_ranges() {
+        let v = vec![(1,'a'), (3,'b'), (4,'c'), (6,'d'), (8,'e')];
+        let w = vec![(4,40), (5,50), (6,60), (9,90), (10,100)];
+
+        let rows = v.merge_intersection(w, |(k,_)| k, |(k,_)| k).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "cd", rows );
+    }
+
+    #[test]
+    fn merge_intersection_keeps_left_duplicates() {
+        let v = vec![1, 2, 2, 3, 3, 3];
+        let w = vec![2, 3, 3];
+
+        assert_eq!( vec![2, 2, 3, 3, 3], v.merge_intersection(w, |x| x, |x| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn set_operations_in_descending_order() {
+        let v = vec![9, 6, 4, 3, 1];
+        let w = vec![10, 6, 5, 3];
+
+        assert_eq!( vec![6, 3], v.clone().merge_intersection(w.clone(), |x| x, |x| x)
+                        .with_order(Order::Descending).collect::<Vec<_>>() );
+        assert_eq!( vec![10, 9, 6, 5, 4, 3, 1], v.clone().union(w.clone(), |x| x, |x| x, |x, _| x)
+                        .with_order(Order::Descending).collect::<Vec<_>>() );
+        assert_eq!( vec![10, 9, 5, 4, 1], v.clone().sym_difference(w.clone(), |x| x, |x| x)
+                        .with_order(Order::Descending).collect::<Vec<_>>() );
+        assert_eq!( vec![10, 9, 6, 6, 5, 4, 3, 3, 1], v.merge_union(w, |x| x, |x| x, false)
+                        .with_order(Order::Descending).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn merge_difference_right_keys_below_above_and_interleaved() {
+        let v = vec![(3,'a'), (4,'b'), (4,'c'), (6,'d')];
+
+        let below = v.clone().merge_difference(vec![0, 1, 2], |(k,_)| k, |k| k).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "abcd", below );
+        let above = v.clone().merge_difference(vec![7, 8], |(k,_)| k, |k| k).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "abcd", above );
+        let interleaved = v.merge_difference(vec![2, 4, 5, 9], |(k,_)| k, |k| k).map(|(_,c)| c).collect::<String>();
+        assert_eq!( "ad", interleaved );
+    }
+
+    #[test]
+    fn merge_difference_passes_the_tail_through() {
+        let v = vec![1, 2, 3, 4, 5, 6];
+        let w = vec![1, 2];
+
+        let mut diff = v.merge_difference(w, |x| x, |x| x);
+        assert_eq!( Some(3), diff.next() );
+        assert_eq!( (3, Some(3)), diff.size_hint() );
+        assert_eq!( vec![4, 5, 6], diff.collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn merge_difference_in_descending_order() {
+        let v = vec![9, 6, 4, 3, 1];
+        let w = vec![10, 6, 5, 3];
+
+        assert_eq!( vec![9, 4, 1], v.merge_difference(w, |x| x, |x| x).with_order(Order::Descending).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn merge_symmetric_difference_of_snapshots() {
+        let before = vec![(1,"ann"), (2,"bo"), (3,"cy"), (5,"ed")];
+        let after = vec![(1,"ann"), (3,"cy"), (4,"di"), (5,"ed")];
+
+        let changes = before.merge_symmetric_difference(after, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![(Side::Left, (2,"bo")), (Side::Right, (4,"di"))], changes );
+    }
+
+    #[test]
+    fn merge_symmetric_difference_interleaves_by_key() {
+        let v = vec![1, 3, 5, 6];
+        let w = vec![2, 3, 4, 7];
+
+        let diff = v.merge_symmetric_difference(w, |x| x).collect::<Vec<_>>();
+        assert_eq!( vec![(Side::Left, 1), (Side::Right, 2), (Side::Right, 4), (Side::Left, 5), (Side::Left, 6), (Side::Right, 7)], diff );
+    }
+}
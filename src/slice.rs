@@ -0,0 +1,432 @@
+//! Joins of sorted slices, indexing into them rather than iterating over their items.
+
+use alloc::vec::Vec;
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+
+/// The first index from `lo` on for which `pred` fails, `pred` holding for a prefix of `v`.
+///
+/// Steps ahead by doubling distances until `pred` fails, then binary searches the last step, so
+/// skipping `n` items takes `O(log n)` calls of `pred`.
+fn gallop<'a,T,P>(v: &'a [T], mut lo: usize, mut pred: P) -> usize where
+    P: FnMut(&'a T) -> bool
+{
+    let mut step = 1;
+    let mut hi = lo;
+    while hi < v.len() && pred(&v[hi]) {
+        lo = hi + 1;
+        hi = lo.saturating_add(step);
+        step = step.saturating_mul(2);
+    }
+
+    let mut hi = hi.min(v.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(&v[mid]) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+
+/// Inner join of a sorted slice with a sorted iterator, created by `join_slice_gallop`.
+///
+/// For every key of the right iterator, the left slice is searched by galloping ahead of the last
+/// match, rather than stepping through the left items one by one. The right items sharing a key
+/// are buffered to pair them with each left item with that key.
+pub struct JoinGallop<'a, A: 'a, J, KI, KJ> where
+    J: Iterator
+{
+    left: &'a [A],
+    j: J,
+    ki: KI,
+    kj: KJ,
+    curr_j: Option<J::Item>,
+    group: Vec<J::Item>,
+    pos: usize,
+    at: usize,
+    end: usize,
+    at_group: usize,
+}
+
+
+/// Joins the sorted slice `left` with the sorted sequence `right` like `join`, skipping ahead in
+/// `left` to the key of every right item with a galloping search.
+///
+/// The rows are the same as those of `left.iter().join(right, ki, kj)`, but the join takes
+/// `O(m log n)` comparisons for `m` right items, making it cheap to join a handful of right items
+/// with a long slice.
+///
+/// ```
+/// use join_it::join_slice_gallop;
+///
+/// let v = (0..1_000_000).collect::<Vec<u32>>();
+/// let w = vec![(10,'a'),(500_000,'b'),(2_000_000,'c')];
+///
+/// let rows = join_slice_gallop(&v, w, |&k| k, |(k,_)| k)
+///     .map(|(&k,(_,c))| (k, c))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![(10,'a'),(500_000,'b')], rows);
+/// ```
+pub fn join_slice_gallop<'a,A,J,KI,KJ,K>(left: &'a [A], right: J, ki: KI, kj: KJ) -> JoinGallop<'a,A,J::IntoIter,KI,KJ> where
+    J: IntoIterator,
+    J::Item: Clone,
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    JoinGallop {
+        left,
+        j: right.into_iter(),
+        ki,
+        kj,
+        curr_j: None,
+        group: Vec::new(),
+        pos: 0,
+        at: 0,
+        end: 0,
+        at_group: 0,
+    }
+}
+
+
+impl<'a,A,J,KI,KJ,K> Iterator for JoinGallop<'a,A,J,KI,KJ> where
+    J: Iterator,
+    J::Item: Clone,
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (&'a A, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let left = self.left;
+
+        loop {
+            if self.at < self.end {
+                let row = (&left[self.at], self.group[self.at_group].clone());
+                self.at_group += 1;
+                if self.at_group == self.group.len() {
+                    self.at_group = 0;
+                    self.at += 1;
+                }
+                return Some(row);
+            }
+
+            let w = self.curr_j.take().or_else(|| self.j.next())?;
+            let k = (self.kj)(w.clone());
+            let ki = &mut self.ki;
+            let start = gallop(left, self.pos, |v| ki(v) < k);
+            if start == left.len() {
+                self.pos = start;
+                return None;
+            }
+
+            self.group.clear();
+            self.group.push(w);
+            for x in self.j.by_ref() {
+                if (self.kj)(x.clone()) != k {
+                    self.curr_j = Some(x);
+                    break;
+                }
+                self.group.push(x);
+            }
+
+            self.pos = gallop(left, start, |v| ki(v) <= k);
+            self.at = start;
+            self.end = self.pos;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let current = (self.end - self.at) * self.group.len() - self.at_group;
+        let later = self.j.size_hint().1
+            .and_then(|hi| hi.checked_add(self.curr_j.is_some() as usize))
+            .and_then(|hi| hi.checked_mul(self.left.len() - self.pos))
+            .and_then(|hi| hi.checked_add(current));
+        (current, later)
+    }
+}
+
+
+impl<'a,A,J,KI,KJ> Clone for JoinGallop<'a,A,J,KI,KJ> where
+    J: Iterator + Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinGallop {
+            left: self.left,
+            j: self.j.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            curr_j: self.curr_j.clone(),
+            group: self.group.clone(),
+            pos: self.pos,
+            at: self.at,
+            end: self.end,
+            at_group: self.at_group,
+        }
+    }
+}
+
+
+impl<'a,A,J,KI,KJ,K> FusedIterator for JoinGallop<'a,A,J,KI,KJ> where
+    J: FusedIterator,
+    J::Item: Clone,
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<'a,A,J,KI,KJ> fmt::Debug for JoinGallop<'a,A,J,KI,KJ> where
+    A: fmt::Debug,
+    J: Iterator + fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinGallop")
+            .field("left", &&self.left[self.at..])
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("curr_j", &self.curr_j)
+            .field("group", &self.group)
+            .finish()
+    }
+}
+
+
+/// Inner join of two sorted slices yielding the indices of the joined items, created by
+/// `join_indices`.
+///
+/// Both slices are indexed in place, so no item is cloned or buffered: the right items sharing a
+/// key are just a range of indices, replayed for every left item with that key.
+pub struct JoinIndices<'a, A: 'a, B: 'a, KI, KJ> {
+    left: &'a [A],
+    right: &'a [B],
+    ki: KI,
+    kj: KJ,
+    i: usize,
+    group: Range<usize>,
+    pos: usize,
+}
+
+
+/// Joins the sorted slices `left` and `right` like `join`, yielding the pair of indices of every
+/// joined row instead of the items.
+///
+/// The key extractors take the items by reference, so the items don't have to be `Clone`.
+///
+/// ```
+/// use join_it::join_indices;
+///
+/// let v = vec![(0,"a".to_string()),(1,"b".to_string()),(2,"c".to_string())];
+/// let w = vec![(1,"x".to_string()),(1,"y".to_string()),(3,"z".to_string())];
+///
+/// let rows = join_indices(&v, &w, |&(k,_)| k, |&(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![(1,0),(1,1)], rows);
+/// ```
+pub fn join_indices<'a,A,B,KI,KJ,K>(left: &'a [A], right: &'a [B], ki: KI, kj: KJ) -> JoinIndices<'a,A,B,KI,KJ> where
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(&'a B) -> K,
+    K: Ord
+{
+    JoinIndices {
+        left,
+        right,
+        ki,
+        kj,
+        i: 0,
+        group: 0..0,
+        pos: 0,
+    }
+}
+
+
+impl<'a,A,B,KI,KJ,K> Iterator for JoinIndices<'a,A,B,KI,KJ> where
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(&'a B) -> K,
+    K: Ord
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (left, right) = (self.left, self.right);
+
+        if self.pos < self.group.end {
+            self.pos += 1;
+            return Some((self.i, self.pos - 1));
+        }
+
+        if !self.group.is_empty() {
+            self.i += 1;
+            if self.i < left.len() && (self.ki)(&left[self.i]) == (self.kj)(&right[self.group.start]) {
+                self.pos = self.group.start + 1;
+                return Some((self.i, self.group.start));
+            }
+            self.group.start = self.group.end;
+        }
+
+        let mut j = self.group.start;
+        while self.i < left.len() && j < right.len() {
+            let k = (self.ki)(&left[self.i]);
+            match Ord::cmp(&k, &(self.kj)(&right[j])) {
+                Ordering::Less => self.i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    let mut end = j + 1;
+                    while end < right.len() && (self.kj)(&right[end]) == k {
+                        end += 1;
+                    }
+                    self.group = j..end;
+                    self.pos = j + 1;
+                    return Some((self.i, j));
+                },
+            }
+        }
+        self.group = j..j;
+        self.pos = j;
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let current = self.group.end - self.pos;
+        let upper = (self.left.len() - self.i.min(self.left.len())).checked_mul(self.right.len() - self.group.start);
+        (current, upper.map(|n| n.max(current)))
+    }
+}
+
+
+impl<'a,A,B,KI,KJ> Clone for JoinIndices<'a,A,B,KI,KJ> where
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinIndices {
+            left: self.left,
+            right: self.right,
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            i: self.i,
+            group: self.group.clone(),
+            pos: self.pos,
+        }
+    }
+}
+
+
+impl<'a,A,B,KI,KJ,K> FusedIterator for JoinIndices<'a,A,B,KI,KJ> where
+    KI: FnMut(&'a A) -> K,
+    KJ: FnMut(&'a B) -> K,
+    K: Ord
+{}
+
+
+impl<'a,A,B,KI,KJ> fmt::Debug for JoinIndices<'a,A,B,KI,KJ> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinIndices")
+            .field("left", &self.left.len())
+            .field("right", &self.right.len())
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("i", &self.i)
+            .field("group", &self.group)
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use tests::assert_exhausted;
+    use std::cell::Cell;
+
+    #[test]
+    fn gallop_matches_join() {
+        let v = (0..2000u32).map(|k| (k / 3, k)).collect::<Vec<_>>();
+        let w = vec![(0,'a'), (5,'b'), (5,'c'), (6,'d'), (400,'e'), (401,'f'), (700,'g'), (700,'h'), (900,'i')];
+
+        let expected = v.iter().join(w.iter().cloned(), |&(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( expected, join_slice_gallop(&v, w.iter().cloned(), |&(k,_)| k, |(k,_)| k).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn gallop_skips_the_gaps() {
+        let v = (0..1_000_000u32).collect::<Vec<_>>();
+        let w = vec![3, 250_000, 250_001, 999_999];
+
+        let calls = Cell::new(0);
+        let rows = join_slice_gallop(&v, w, |&k| { calls.set(calls.get() + 1); k }, |k| k).collect::<Vec<_>>();
+        assert_eq!( vec![(&3, 3), (&250_000, 250_000), (&250_001, 250_001), (&999_999, 999_999)], rows );
+        assert!( calls.get() < 4 * 4 * 20, "{} key extractions", calls.get() );
+    }
+
+    #[test]
+    fn gallop_without_matches() {
+        let v = vec![1, 3, 5];
+        let empty: Vec<u32> = vec![];
+
+        assert_eq!( 0, join_slice_gallop(&v, vec![0, 2, 4, 6, 8], |&k| k, |k| k).count() );
+        assert_eq!( 0, join_slice_gallop(&v, empty.clone(), |&k| k, |k| k).count() );
+        assert_eq!( 0, join_slice_gallop(&empty, vec![1], |&k| k, |k| k).count() );
+    }
+
+    #[test]
+    fn indices_address_equal_keys() {
+        let v = vec![(0,'a'), (1,'b'), (1,'c'), (3,'d'), (4,'e')];
+        let w = vec![(1,10), (1,11), (2,20), (4,40), (4,41)];
+
+        let rows = join_indices(&v, &w, |&(k,_)| k, |&(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![(1,0), (1,1), (2,0), (2,1), (4,3), (4,4)], rows );
+        for &(a, b) in &rows {
+            assert_eq!( v[a].0, w[b].0 );
+        }
+
+        let expected = v.iter().join(w.iter(), |&(k,_)| k, |&(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( expected, rows.iter().map(|&(a, b)| (&v[a], &w[b])).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    fn indices_without_matches() {
+        let empty: Vec<u32> = vec![];
+
+        assert_eq!( 0, join_indices(&[1, 3], &[0, 2, 4], |&k| k, |&k| k).count() );
+        assert_eq!( 0, join_indices(&empty, &[1], |&k| k, |&k| k).count() );
+        assert_eq!( 0, join_indices(&[1], &empty, |&k| k, |&k| k).count() );
+    }
+
+    #[test]
+    fn slice_joins_stay_exhausted() {
+        let v = vec![1, 5];
+        let w = vec![1, 2, 3];
+
+        let mut rows = join_indices(&v, &w, |&k| k, |&k| k);
+        assert_eq!( Some((0, 0)), rows.next() );
+        for _ in 0..3 {
+            assert_eq!( None, rows.next() );
+            assert_eq!( (0, Some(0)), rows.size_hint() );
+        }
+        assert_exhausted(join_slice_gallop(&v, w, |&k| k, |k| k));
+    }
+
+    #[test]
+    fn gallop_long_duplicate_groups() {
+        let v = (0..20).map(|k| k / 10).collect::<Vec<_>>();
+        let w = vec![1, 1, 5];
+
+        let rows = join_slice_gallop(&v, w.clone(), |&k| k, |k| k).collect::<Vec<_>>();
+        assert_eq!( v.iter().join(w, |&k| k, |k| k).collect::<Vec<_>>(), rows );
+        assert_eq!( 20, rows.len() );
+    }
+}
@@ -0,0 +1,835 @@
+//! Checks of the sort order and the uniqueness of keys the joins rely on.
+
+use alloc::vec::{self, Vec};
+use core::cmp::Ord;
+use core::fmt;
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use std::error::Error;
+use fallible::{Stash, TryMap};
+use merge::{Keys, Merge, Row};
+use {JoinIt, Joinable};
+
+
+/// Iterator adaptor asserting that the keys of the items never decrease.
+///
+/// Joining unsorted sequences doesn't fail, it just quietly gives a wrong result. Wrapping the
+/// sequences in `AssertSorted` makes debug builds panic on the first key that is less than the key
+/// before it. In release builds the check is compiled out and the items are passed through as is.
+///
+/// ```
+/// use join_it::{AssertSorted, Joinable};
+///
+/// let v = AssertSorted::new(vec![(0,'a'),(1,'b'),(2,'c')], |(k,_)| k);
+/// let w = AssertSorted::new(vec![(1,11),(2,22)], |(k,_)| k);
+///
+/// assert_eq!(2, v.join(w, |(k,_)| k, |(k,_)| k).count());
+/// ```
+pub struct AssertSorted<I, K, KF> {
+    iter: I,
+    key: KF,
+    prev: Option<K>,
+}
+
+
+/// Inner join of two sequences wrapped in `AssertSorted`, created by `Joinable::join_checked`.
+pub type JoinChecked<I, J, K, KI, KJ> = JoinIt<AssertSorted<I, K, KI>, AssertSorted<J, K, KJ>, KI, KJ>;
+
+
+/// Inner join of two sequences sorted into `Vec`s beforehand, created by `sort_and_join`.
+pub type SortedJoin<A, B, KI, KJ> = JoinIt<vec::IntoIter<A>, vec::IntoIter<B>, KI, KJ>;
+
+
+/// Collects `i` and `j` into `Vec`s sorted by the key extractors `ki` and `kj`, then joins them.
+///
+/// The sort is stable and extracts the key of every item once, the join then extracts them again
+/// as it merges the two `Vec`s, which it holds on to.
+///
+/// ```
+/// use join_it::sort_and_join;
+///
+/// let v = vec![(2,'c'),(0,'a'),(1,'b')];
+/// let w = vec![(1,11),(3,33),(2,22)];
+///
+/// let rows = sort_and_join(v, w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+/// assert_eq!(vec![((1,'b'),(1,11)), ((2,'c'),(2,22))], rows);
+/// ```
+pub fn sort_and_join<I,J,K,KI,KJ>( i: I, j: J, mut ki: KI, mut kj: KJ ) -> SortedJoin<I::Item,J::Item,KI,KJ> where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    let mut v = i.into_iter().collect::<Vec<_>>();
+    let mut w = j.into_iter().collect::<Vec<_>>();
+    v.sort_by_cached_key(|v| ki(v.clone()));
+    w.sort_by_cached_key(|w| kj(w.clone()));
+
+    v.join(w, ki, kj)
+}
+
+
+impl<I,K,KF> AssertSorted<I,K,KF> where
+    I: Iterator
+{
+    /// Wraps `iter`, checking the order of the keys extracted by `key`.
+    pub fn new<II>(iter: II, key: KF) -> Self where
+        II: IntoIterator<IntoIter = I, Item = I::Item>
+    {
+        AssertSorted {
+            iter: iter.into_iter(),
+            key,
+            prev: None,
+        }
+    }
+}
+
+
+impl<I,K,KF> Iterator for AssertSorted<I,K,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord + fmt::Debug
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+
+        if cfg!(debug_assertions) {
+            let k = (self.key)(v.clone());
+            if let Some(ref prev) = self.prev {
+                assert!(*prev <= k, "AssertSorted: key {:?} follows the greater key {:?}", k, prev);
+            }
+            self.prev = Some(k);
+        }
+        Some(v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<I,K,KF> Clone for AssertSorted<I,K,KF> where
+    I: Clone,
+    K: Clone,
+    KF: Clone
+{
+    fn clone(&self) -> Self {
+        AssertSorted {
+            iter: self.iter.clone(),
+            key: self.key.clone(),
+            prev: self.prev.clone(),
+        }
+    }
+}
+
+
+impl<I,K,KF> FusedIterator for AssertSorted<I,K,KF> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: Ord + fmt::Debug
+{}
+
+
+impl<I,K,KF> fmt::Debug for AssertSorted<I,K,KF> where
+    I: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AssertSorted")
+            .field("iter", &self.iter)
+            .field("key", &"<key_fn>")
+            .field("prev", &self.prev)
+            .finish()
+    }
+}
+
+
+/// Iterator adaptor asserting that no two consecutive items share a key, created by
+/// `assert_unique_by_key`.
+///
+/// Unlike `AssertSorted`, the check is kept in release builds. Only the key of the previous item is
+/// remembered, so in a sorted sequence every key is checked to be unique.
+pub struct AssertUnique<I, K, KF> {
+    iter: I,
+    key: KF,
+    prev: Option<K>,
+}
+
+
+/// Wraps the sorted `iter`, panicking as soon as two consecutive items have keys, as extracted by
+/// `key`, that are equal.
+///
+/// ```
+/// use join_it::{assert_unique_by_key, Joinable};
+///
+/// let v = assert_unique_by_key(vec![(0,'a'),(1,'b'),(2,'c')], |(k,_)| k);
+/// let w = vec![(1,11),(1,12)];
+///
+/// assert_eq!(2, v.join(w, |(k,_)| k, |(k,_)| k).count());
+/// ```
+pub fn assert_unique_by_key<I,KF,K>(iter: I, key: KF) -> AssertUnique<I::IntoIter,K,KF> where
+    I: IntoIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq + fmt::Debug
+{
+    AssertUnique {
+        iter: iter.into_iter(),
+        key,
+        prev: None,
+    }
+}
+
+
+impl<I,K,KF> Iterator for AssertUnique<I,K,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq + fmt::Debug
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+        let k = (self.key)(v.clone());
+
+        assert!(self.prev.as_ref() != Some(&k), "AssertUnique: key {:?} appears twice in a row", k);
+        self.prev = Some(k);
+        Some(v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<I,K,KF> Clone for AssertUnique<I,K,KF> where
+    I: Clone,
+    K: Clone,
+    KF: Clone
+{
+    fn clone(&self) -> Self {
+        AssertUnique {
+            iter: self.iter.clone(),
+            key: self.key.clone(),
+            prev: self.prev.clone(),
+        }
+    }
+}
+
+
+impl<I,K,KF> FusedIterator for AssertUnique<I,K,KF> where
+    I: FusedIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq + fmt::Debug
+{}
+
+
+impl<I,K,KF> fmt::Debug for AssertUnique<I,K,KF> where
+    I: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AssertUnique")
+            .field("iter", &self.iter)
+            .field("key", &"<key_fn>")
+            .field("prev", &self.prev)
+            .finish()
+    }
+}
+
+
+/// Iterator adaptor checking that no two consecutive items share a key, created by
+/// `try_unique_by_key`.
+///
+/// The first repeated key is returned as a `JoinError::Duplicate`, after which the iterator is
+/// exhausted.
+pub struct TryUnique<I, K, KF> {
+    iter: I,
+    key: KF,
+    prev: Option<K>,
+    failed: bool,
+}
+
+
+/// Wraps the sorted `iter` like `assert_unique_by_key`, returning an error instead of panicking.
+///
+/// ```
+/// use join_it::{try_unique_by_key, JoinError};
+///
+/// let rows = try_unique_by_key(vec![1,2,2,3], |k| k).collect::<Vec<_>>();
+/// assert_eq!(vec![Ok(1), Ok(2), Err(JoinError::Duplicate { key: 2 })], rows);
+/// ```
+pub fn try_unique_by_key<I,KF,K>(iter: I, key: KF) -> TryUnique<I::IntoIter,K,KF> where
+    I: IntoIterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{
+    TryUnique {
+        iter: iter.into_iter(),
+        key,
+        prev: None,
+        failed: false,
+    }
+}
+
+
+impl<I,K,KF> Iterator for TryUnique<I,K,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{
+    type Item = Result<I::Item, JoinError<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let v = self.iter.next()?;
+        let k = (self.key)(v.clone());
+
+        if self.prev.as_ref() == Some(&k) {
+            self.failed = true;
+            return Some(Err(JoinError::Duplicate { key: k }));
+        }
+        self.prev = Some(k);
+        Some(Ok(v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            (0, Some(0))
+        } else {
+            let (lo, hi) = self.iter.size_hint();
+            (lo.min(1), hi)
+        }
+    }
+}
+
+
+impl<I,K,KF> Clone for TryUnique<I,K,KF> where
+    I: Clone,
+    K: Clone,
+    KF: Clone
+{
+    fn clone(&self) -> Self {
+        TryUnique {
+            iter: self.iter.clone(),
+            key: self.key.clone(),
+            prev: self.prev.clone(),
+            failed: self.failed,
+        }
+    }
+}
+
+
+impl<I,K,KF> FusedIterator for TryUnique<I,K,KF> where
+    I: Iterator,
+    I::Item: Clone,
+    KF: FnMut(I::Item) -> K,
+    K: PartialEq
+{}
+
+
+impl<I,K,KF> fmt::Debug for TryUnique<I,K,KF> where
+    I: fmt::Debug,
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TryUnique")
+            .field("iter", &self.iter)
+            .field("key", &"<key_fn>")
+            .field("prev", &self.prev)
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+
+/// A sequence known to be sorted by the key of its items.
+///
+/// `Sorted` carries the sort order in the type, for pipelines where the ordering is established
+/// once and relied upon later. It's either checked by `try_new`, which buffers the items, or
+/// asserted by the caller with `new_unchecked`. Two `Sorted` sequences are joined with
+/// `sorted_join`, which needs no further checks beyond those every join does in debug builds.
+///
+/// ```
+/// use join_it::Sorted;
+///
+/// let v = Sorted::try_new(vec![(0,'a'),(1,'b'),(2,'c')], |(k,_)| k).unwrap();
+/// let w = Sorted::try_new(vec![(2,22),(1,11)], |(k,_)| k);
+/// assert!(w.is_none());
+///
+/// let w = Sorted::new_unchecked(vec![(1,11),(2,22)]);
+/// let rows = v.sorted_join(w, |(k,_)| k, |(k,_)| k)
+///     .map(|((_,a),(_,b))| (a, b))
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![('b',11),('c',22)], rows);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sorted<I> {
+    iter: I,
+}
+
+
+impl<I> Sorted<I> where
+    I: Iterator
+{
+    /// Wraps `iter` without checking it, the caller vouching for its items being sorted.
+    pub fn new_unchecked<II>(iter: II) -> Self where
+        II: IntoIterator<IntoIter = I, Item = I::Item>
+    {
+        Sorted { iter: iter.into_iter() }
+    }
+
+    /// Unwraps the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    /// Inner join with another sorted sequence, like `Joinable::join`.
+    ///
+    /// The keys extracted by `ki` and `kj` have to give the same order as the ones the sequences
+    /// are sorted by.
+    pub fn sorted_join<J,KI,KJ,K>(self, other: Sorted<J>, ki: KI, kj: KJ) -> JoinIt<I,J,KI,KJ> where
+        I::Item: Clone,
+        J: Iterator,
+        J::Item: Clone,
+        KI: FnMut(I::Item) -> K,
+        KJ: FnMut(J::Item) -> K
+    {
+        self.iter.join(other.iter, ki, kj)
+    }
+}
+
+
+impl<T> Sorted<vec::IntoIter<T>> where
+    T: Clone
+{
+    /// Collects `iter` and checks that the keys extracted by `key` never decrease, returning
+    /// `None` if they do.
+    ///
+    /// The items are buffered, so the returned sequence can be cloned to go over them again.
+    pub fn try_new<II,K,KF>(iter: II, mut key: KF) -> Option<Self> where
+        II: IntoIterator<Item = T>,
+        KF: FnMut(T) -> K,
+        K: Ord
+    {
+        let items = iter.into_iter().collect::<Vec<_>>();
+        let sorted = items.windows(2).all(|pair| key(pair[0].clone()) <= key(pair[1].clone()));
+
+        if sorted {
+            Some(Sorted { iter: items.into_iter() })
+        } else {
+            None
+        }
+    }
+}
+
+
+impl<I> Iterator for Sorted<I> where
+    I: Iterator
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+impl<I> FusedIterator for Sorted<I> where
+    I: FusedIterator
+{}
+
+
+/// A side of a join or of a set operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+
+/// The error returned by the validating join `Joinable::try_join` and by `try_unique_by_key`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JoinError<K> {
+    /// The keys of a side went backwards, to `key`.
+    Unsorted { side: Side, key: K },
+    /// Two consecutive items share `key`.
+    Duplicate { key: K },
+}
+
+
+impl<K> fmt::Display for JoinError<K> where
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JoinError::Unsorted { side, ref key } =>
+                write!(f, "the {:?} sequence isn't sorted, key {:?} is out of order", side, key),
+            JoinError::Duplicate { ref key } =>
+                write!(f, "the key {:?} isn't unique", key),
+        }
+    }
+}
+
+
+#[cfg(feature = "std")]
+impl<K> Error for JoinError<K> where
+    K: fmt::Debug
+{}
+
+
+/// Checks that the keys of a `Stash`ed sequence never go backwards, stashing the first key that
+/// does.
+pub(crate) struct CheckSorted<KF, K> {
+    key: KF,
+    prev: Option<K>,
+}
+
+
+impl<KF,K> CheckSorted<KF,K> {
+    fn new(key: KF) -> Self {
+        CheckSorted { key, prev: None }
+    }
+}
+
+
+impl<A,KF,K> TryMap<A> for CheckSorted<KF,K> where
+    A: Clone,
+    KF: FnMut(A) -> K,
+    K: Ord
+{
+    type Value = A;
+    type Error = K;
+
+    fn try_map(&mut self, a: A) -> Result<A, K> {
+        let k = (self.key)(a.clone());
+        if self.prev.as_ref().map_or(false, |prev| *prev > k) {
+            return Err(k);
+        }
+        self.prev = Some(k);
+        Ok(a)
+    }
+}
+
+
+impl<KF,K> Clone for CheckSorted<KF,K> where
+    KF: Clone,
+    K: Clone
+{
+    fn clone(&self) -> Self {
+        CheckSorted {
+            key: self.key.clone(),
+            prev: self.prev.clone(),
+        }
+    }
+}
+
+
+impl<KF,K> fmt::Debug for CheckSorted<KF,K> where
+    K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CheckSorted")
+            .field("key", &"<key_fn>")
+            .field("prev", &self.prev)
+            .finish()
+    }
+}
+
+
+/// Yields the items of a sequence until their keys go backwards, stashing that key.
+pub(crate) type SortedStash<I, KF, K> = Stash<I, CheckSorted<KF, K>>;
+
+
+/// Inner join validating that both sequences are sorted in ascending key order, created by
+/// `Joinable::try_join`.
+///
+/// The keys are checked in release builds too. The first key that goes backwards is returned as a
+/// `JoinError`, after which the iterator is exhausted. Only the items the join gets to are checked,
+/// so a side may not be looked at past the end of the other.
+pub struct TryJoin<I, J, KI, KJ, K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    pub(crate) merge: Merge<SortedStash<I, KI, K>, SortedStash<J, KJ, K>>,
+    pub(crate) ki: KI,
+    pub(crate) kj: KJ,
+    pub(crate) done: bool,
+}
+
+
+impl<I,J,KI,KJ,K> TryJoin<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K + Clone,
+    KJ: FnMut(J::Item) -> K + Clone,
+    K: Ord
+{
+    pub(crate) fn new(i: I, j: J, ki: KI, kj: KJ) -> Self {
+        TryJoin {
+            merge: Merge::new(Stash::new(i, CheckSorted::new(ki.clone())), Stash::new(j, CheckSorted::new(kj.clone())), false, false),
+            ki,
+            kj,
+            done: false,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for TryJoin<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = Result<(I::Item, J::Item), JoinError<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if let Some(Row::Both(v, w)) = self.merge.next_with(&mut Keys(&mut self.ki, &mut self.kj)) {
+            return Some(Ok((v, w)));
+        }
+        self.done = true;
+
+        let (i, j) = self.merge.iters_mut();
+        match (i.err.take(), j.err.take()) {
+            (Some(key), _) => Some(Err(JoinError::Unsorted { side: Side::Left, key })),
+            (_, Some(key)) => Some(Err(JoinError::Unsorted { side: Side::Right, key })),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (_, hi) = self.merge.size_hint();
+        (0, hi.and_then(|hi| hi.checked_add(1)))
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Clone for TryJoin<I,J,KI,KJ,K> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K + Clone,
+    KJ: FnMut(J::Item) -> K + Clone,
+    K: Ord + Clone
+{
+    fn clone(&self) -> Self {
+        TryJoin {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+            done: self.done,
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for TryJoin<I,J,KI,KJ,K> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ,K> fmt::Debug for TryJoin<I,J,KI,KJ,K> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: Clone + fmt::Debug,
+    J::Item: Clone + fmt::Debug,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TryJoin")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use tests::assert_exhausted;
+
+    #[test]
+    fn assert_sorted_passes_items_through() {
+        let v = vec![(0,'a'), (1,'b'), (1,'c'), (3,'d')];
+
+        assert_eq!( v.clone(), AssertSorted::new(v, |(x,_)| x).collect::<Vec<_>>() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "key 1 follows the greater key 2")]
+    fn assert_sorted_panics_on_unsorted_keys() {
+        let v = vec![(0,'a'), (2,'c'), (1,'b')];
+
+        AssertSorted::new(v, |(x,_)| x).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "key 2 appears twice in a row")]
+    fn assert_unique_panics_on_a_repeated_key() {
+        let v = vec![(1,'a'), (2,'b'), (2,'c'), (3,'d')];
+
+        assert_unique_by_key(v, |(x,_)| x).count();
+    }
+
+    #[test]
+    fn assert_unique_passes_unique_keys_through() {
+        let v = vec![1, 2, 4];
+
+        assert_eq!( vec![1, 2, 4], assert_unique_by_key(v, |x| x).collect::<Vec<_>>() );
+        assert_eq!( 0, assert_unique_by_key(Vec::<u32>::new(), |x| x).count() );
+    }
+
+    #[test]
+    fn key_checks_stay_exhausted() {
+        let v = [0, 1, 1, 3];
+
+        assert_exhausted(AssertSorted::new(v.iter(), |&x| x));
+        assert_exhausted(assert_unique_by_key(v[2..].iter(), |&x| x));
+        assert_exhausted(try_unique_by_key(v.iter(), |&x| x));
+    }
+
+    #[test]
+    fn try_unique_stops_at_the_first_repeat() {
+        let mut unique = try_unique_by_key(vec![(0,'a'), (1,'b'), (1,'c'), (1,'d')], |(x,_)| x);
+        assert_eq!( Some(Ok((0,'a'))), unique.next() );
+        assert_eq!( Some(Ok((1,'b'))), unique.next() );
+        assert_eq!( Some(Err(JoinError::Duplicate { key: 1 })), unique.next() );
+        assert_eq!( None, unique.next() );
+        assert_eq!( "the key 1 isn't unique", JoinError::Duplicate { key: 1 }.to_string() );
+    }
+
+    #[test]
+    fn join_checked() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,11), (2,22)];
+
+        let join_it = v.iter().join_checked(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|(&(_,a),&(_,b))| (a, b));
+
+        assert_eq!( vec![('b',11), ('c',22)], join_it.collect::<Vec<(char,u32)>>() );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "AssertSorted")]
+    fn join_checked_panics_on_unsorted_right_side() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(2,22), (1,11)];
+
+        v.iter().join_checked(w.iter(), |&(x,_)| x, |&(x,_)| x).count();
+    }
+
+    #[test]
+    fn sorted_try_new() {
+        let empty: Vec<u32> = vec![];
+
+        assert_eq!( Some(vec![1, 2, 2, 3]), Sorted::try_new(vec![1, 2, 2, 3], |x| x).map(|s| s.collect::<Vec<_>>()) );
+        assert!( Sorted::try_new(vec![1, 3, 2], |x| x).is_none() );
+        assert!( Sorted::try_new(vec![3, 2, 1], |x: u32| std::cmp::Reverse(x)).is_some() );
+        assert_eq!( Some(0), Sorted::try_new(empty, |x| x).map(|s| s.count()) );
+    }
+
+    #[test]
+    fn sorted_join_rewound() {
+        let v = Sorted::try_new(vec![(0,'a'), (1,'b'), (1,'c')], |(x,_)| x).unwrap();
+        let w = Sorted::try_new(vec![(1,10), (2,20)], |(x,_)| x).unwrap();
+
+        let first = v.clone().sorted_join(w.clone(), |(x,_)| x, |(x,_)| x).collect::<Vec<_>>();
+        let again = v.sorted_join(w, |(x,_)| x, |(x,_)| x).collect::<Vec<_>>();
+        assert_eq!( vec![((1,'b'),(1,10)), ((1,'c'),(1,10))], first );
+        assert_eq!( first, again );
+    }
+
+    #[test]
+    fn try_join_sorted() {
+        let v = [(0,'a'), (1,'b'), (1,'c'), (2,'d')];
+        let w = [(1,10), (2,20), (3,30)];
+
+        let rows = v.iter().try_join(w.iter(), |&(x,_)| x, |&(x,_)| x)
+            .map(|row| row.map(|(&(_,a),&(_,b))| (a, b)))
+            .collect::<Result<Vec<_>,_>>();
+        assert_eq!( Ok(vec![('b',10), ('c',10), ('d',20)]), rows );
+    }
+
+    #[test]
+    fn try_join_descending_input() {
+        let v = [(0,'a'), (1,'b'), (2,'c')];
+        let w = [(1,10), (0,0)];
+
+        let mut join_it = v.iter().try_join(w.iter(), |&(x,_)| x, |&(x,_)| x);
+        assert_eq!( Some(Ok((&(1,'b'),&(1,10)))), join_it.next() );
+        assert_eq!( Some(Err(JoinError::Unsorted { side: Side::Right, key: 0 })), join_it.next() );
+        assert_eq!( None, join_it.next() );
+        assert_eq!( (0, Some(0)), join_it.size_hint() );
+    }
+
+    #[test]
+    fn try_join_unsorted_left_side() {
+        let v = [(3,'d'), (1,'b')];
+        let w = [(0,0), (1,10), (3,30)];
+
+        let rows = v.iter().try_join(w.iter(), |&(x,_)| x, |&(x,_)| x).collect::<Vec<_>>();
+        assert_eq!( vec![Ok((&(3,'d'),&(3,30))), Err(JoinError::Unsorted { side: Side::Left, key: 1 })], rows );
+        assert_eq!( "the Left sequence isn't sorted, key 1 is out of order",
+                    JoinError::Unsorted { side: Side::Left, key: 1 }.to_string() );
+    }
+
+    #[test]
+    fn sort_and_join_matches_joining_sorted_vecs() {
+        let v = vec![(3,'d'), (1,'a'), (2,'c'), (1,'b')];
+        let w = vec![(2,20), (1,10), (4,40), (1,11)];
+
+        let rows = v.clone().into_sorted_join(w.clone(), |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        assert_eq!( vec![((1,'a'), (1,10)), ((1,'a'), (1,11)), ((1,'b'), (1,10)), ((1,'b'), (1,11)), ((2,'c'), (2,20))], rows );
+        assert_eq!( rows, sort_and_join(v, w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>() );
+    }
+}
@@ -0,0 +1,464 @@
+//! Diagnostics of how a join advanced through its sequences, and where it found its rows.
+
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::iter::FusedIterator;
+use merge::{Compare, Keys, Merge, Row};
+use JoinIt;
+
+
+/// Counts of the steps a join took, filled in by `JoinIt::with_stats`.
+///
+/// The counts are kept up to date as the join goes, so they're also right for a join that was
+/// only partly consumed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JoinStats {
+    /// The left items stepped past because their key was less than the key of the right item.
+    pub left_advanced: usize,
+    /// The right items stepped past because their key was less than the key of the left item.
+    pub right_advanced: usize,
+    /// The joined rows returned.
+    pub matched: usize,
+}
+
+
+/// Inner join counting its steps into a `JoinStats`, created by `JoinIt::with_stats`.
+pub struct JoinWithStats<'a, I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    join: JoinIt<I, J, KI, KJ>,
+    stats: &'a mut JoinStats,
+}
+
+
+/// Passes the comparisons on to `C`, counting the items the merge skips.
+struct Counted<'a, C> {
+    compare: C,
+    stats: &'a mut JoinStats,
+}
+
+
+impl<'a,A,B,C> Compare<A,B> for Counted<'a,C> where
+    C: Compare<A,B>
+{
+    fn cmp(&mut self, v: &A, w: &B) -> Ordering {
+        self.compare.cmp(v, w)
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, v0: &A, v1: &A) -> Option<Ordering> {
+        self.compare.cmp_left(v0, v1)
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, w0: &B, w1: &B) -> Option<Ordering> {
+        self.compare.cmp_right(w0, w1)
+    }
+
+    fn skip_left(&mut self, _v: &A) {
+        self.stats.left_advanced += 1;
+    }
+
+    fn skip_right(&mut self, _w: &B) {
+        self.stats.right_advanced += 1;
+    }
+}
+
+
+/// Numbers the items of an iterator, keeping count of the items taken so far.
+#[derive(Clone, Debug)]
+struct Positions<I> {
+    iter: I,
+    pos: usize,
+}
+
+
+impl<I> Positions<I> {
+    fn new(iter: I) -> Self {
+        Positions { iter, pos: 0 }
+    }
+}
+
+
+impl<I> Iterator for Positions<I> where
+    I: Iterator
+{
+    type Item = (usize, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+        self.pos += 1;
+        Some((self.pos - 1, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+
+/// Inner join pairing every item of a row with its position in its sequence, created by
+/// `JoinIt::with_positions`.
+///
+/// The positions count every item taken from a sequence, including the ones the join stepped past
+/// without a match.
+pub struct JoinWithPos<I, J, KI, KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    merge: Merge<Positions<I>, Positions<J>>,
+    ki: KI,
+    kj: KJ,
+}
+
+
+impl<I,J,KI,KJ> JoinIt<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// Counts the items this join steps past and the rows it returns into `stats`, adding them to
+    /// the counts already there.
+    ///
+    /// ```
+    /// use join_it::{Joinable, JoinStats};
+    ///
+    /// let v = vec![0,1,2,4];
+    /// let w = vec![1,3,4];
+    ///
+    /// let mut stats = JoinStats::default();
+    /// let rows = v.join(w, |k| k, |k| k).with_stats(&mut stats).collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,1),(4,4)], rows);
+    /// assert_eq!(JoinStats { left_advanced: 2, right_advanced: 1, matched: 2 }, stats);
+    /// ```
+    pub fn with_stats<'a>(self, stats: &'a mut JoinStats) -> JoinWithStats<'a,I,J,KI,KJ> {
+        JoinWithStats { join: self, stats }
+    }
+
+    /// Pairs every item of the rows of this join with its position in its sequence, counting from
+    /// zero.
+    ///
+    /// Panics if the join has already been advanced.
+    ///
+    /// ```
+    /// use join_it::Joinable;
+    ///
+    /// let v = vec!['a','b','c'];
+    /// let w = vec!['b','c','c'];
+    ///
+    /// let rows = v.join(w, |c| c, |c| c).with_positions().collect::<Vec<_>>();
+    /// assert_eq!(vec![(1,'b',0,'b'), (2,'c',1,'c'), (2,'c',2,'c')], rows);
+    /// ```
+    pub fn with_positions(self) -> JoinWithPos<I,J,KI,KJ> {
+        JoinWithPos {
+            merge: self.merge.map_iters(Positions::new, Positions::new),
+            ki: self.ki,
+            kj: self.kj,
+        }
+    }
+}
+
+
+impl<'a,I,J,KI,KJ,K> Iterator for JoinWithStats<'a,I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let join = &mut self.join;
+        let mut compare = Counted { compare: Keys(&mut join.ki, &mut join.kj), stats: &mut *self.stats };
+
+        let row = match join.merge.next_with(&mut compare) {
+            Some(Row::Both(v, w)) => Some((v, w)),
+            _ => join.merge.next_in_back_group(),
+        };
+        if row.is_some() {
+            compare.stats.matched += 1;
+        }
+        row
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.join.size_hint()
+    }
+}
+
+
+impl<'a,I,J,KI,KJ,K> FusedIterator for JoinWithStats<'a,I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<'a,I,J,KI,KJ> fmt::Debug for JoinWithStats<'a,I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinWithStats")
+            .field("join", &self.join)
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+
+impl<I,J,KI,KJ> JoinWithPos<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator
+{
+    /// The number of items taken from the left sequence so far, including the one the join holds
+    /// on to for the next row.
+    pub fn left_pos(&self) -> usize {
+        self.merge.iters().0.pos
+    }
+
+    /// The number of items taken from the right sequence so far, like `left_pos`.
+    pub fn right_pos(&self) -> usize {
+        self.merge.iters().1.pos
+    }
+}
+
+
+impl<I,J,KI,KJ,K> Iterator for JoinWithPos<I,J,KI,KJ> where
+    I: Iterator,
+    J: Iterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{
+    type Item = (usize, I::Item, usize, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (ki, kj) = (&mut self.ki, &mut self.kj);
+        let mut left = |(_, v)| ki(v);
+        let mut right = |(_, w)| kj(w);
+
+        match self.merge.next_with(&mut Keys(&mut left, &mut right)) {
+            Some(Row::Both((m, v), (n, w))) => Some((m, v, n, w)),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.merge.size_hint()
+    }
+}
+
+
+impl<I,J,KI,KJ> Clone for JoinWithPos<I,J,KI,KJ> where
+    I: Iterator + Clone,
+    J: Iterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Clone,
+    KJ: Clone
+{
+    fn clone(&self) -> Self {
+        JoinWithPos {
+            merge: self.merge.clone(),
+            ki: self.ki.clone(),
+            kj: self.kj.clone(),
+        }
+    }
+}
+
+
+impl<I,J,KI,KJ,K> FusedIterator for JoinWithPos<I,J,KI,KJ> where
+    I: FusedIterator,
+    J: FusedIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: FnMut(I::Item) -> K,
+    KJ: FnMut(J::Item) -> K,
+    K: Ord
+{}
+
+
+impl<I,J,KI,KJ> fmt::Debug for JoinWithPos<I,J,KI,KJ> where
+    I: Iterator + fmt::Debug,
+    J: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    J::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinWithPos")
+            .field("merge", &self.merge)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .finish()
+    }
+}
+
+
+/// A step of `join_it_observed` past an item without a match, with the key of that item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AdvanceEvent<K> {
+    /// A left item was stepped past, its key being less than the key of the right item.
+    Left(K),
+    /// A right item was stepped past, its key being less than the key of the left item.
+    Right(K),
+}
+
+
+/// Compares items by their keys, reporting the items the merge skips to `on_advance`.
+struct Observed<'a, KI: 'a, KJ: 'a, A> {
+    ki: &'a KI,
+    kj: &'a KJ,
+    on_advance: A,
+}
+
+
+impl<'a,V,W,K,KI,KJ,A> Compare<V,W> for Observed<'a,KI,KJ,A> where
+    V: Clone,
+    W: Clone,
+    KI: Fn(V) -> K,
+    KJ: Fn(W) -> K,
+    A: FnMut(AdvanceEvent<K>),
+    K: Ord
+{
+    fn cmp(&mut self, v: &V, w: &W) -> Ordering {
+        Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(w.clone()))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_left(&mut self, v0: &V, v1: &V) -> Option<Ordering> {
+        Some(Ord::cmp(&(self.ki)(v0.clone()), &(self.ki)(v1.clone())))
+    }
+
+    #[cfg(debug_assertions)]
+    fn cmp_right(&mut self, w0: &W, w1: &W) -> Option<Ordering> {
+        Some(Ord::cmp(&(self.kj)(w0.clone()), &(self.kj)(w1.clone())))
+    }
+
+    fn skip_left(&mut self, v: &V) {
+        (self.on_advance)(AdvanceEvent::Left((self.ki)(v.clone())));
+    }
+
+    fn skip_right(&mut self, w: &W) {
+        (self.on_advance)(AdvanceEvent::Right((self.kj)(w.clone())));
+    }
+}
+
+
+/// Maps f over the join between `i` and `j` like `join_it`, calling `on_advance` whenever the join
+/// steps past an item of either side without a match.
+///
+/// The steps taken while matching go to `f` as the rows, so together the two closures see every
+/// item the join took, which makes for progress reports on long joins.
+///
+/// ```
+/// use join_it::{join_it_observed, AdvanceEvent};
+/// let v = vec![(0,'a'),(1,'b'),(3,'d')];
+/// let w = vec![(1,11),(2,22),(3,33)];
+/// let mut skipped = Vec::new();
+/// join_it_observed(v, w, |(k,_)| k, |(k,_)| k, |(k0,_),(k1,_)| assert_eq!(k0, k1), |event| skipped.push(event));
+/// assert_eq!(vec![AdvanceEvent::Left(0), AdvanceEvent::Right(2)], skipped);
+/// ```
+pub fn join_it_observed<I,J,K,KI,KJ,F,A>( i: I, j: J, ki: KI, kj: KJ, mut f: F, on_advance: A ) where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+    KI: Fn(I::Item) -> K,
+    KJ: Fn(J::Item) -> K,
+    F: FnMut(I::Item, J::Item),
+    A: FnMut(AdvanceEvent<K>),
+    K: Ord
+{
+    let mut merge = Merge::new(i.into_iter(), j.into_iter(), false, false);
+    let mut observed = Observed { ki: &ki, kj: &kj, on_advance };
+
+    while let Some(Row::Both(v, w)) = merge.next_with(&mut observed) {
+        f(v, w);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    #[test]
+    fn with_stats_duplicate_keys() {
+        let v = vec![1, 1, 2, 5, 6];
+        let w = vec![0, 1, 1, 3, 4, 6];
+
+        let mut stats = JoinStats::default();
+        assert_eq!( 5, v.join(w, |k| k, |k| k).with_stats(&mut stats).count() );
+        assert_eq!( JoinStats { left_advanced: 2, right_advanced: 3, matched: 5 }, stats );
+    }
+
+    #[test]
+    fn with_stats_partly_consumed() {
+        let v = vec![0, 2, 4, 6];
+        let w = vec![1, 2, 3, 4, 5, 6];
+
+        let mut stats = JoinStats::default();
+        {
+            let mut rows = v.join(w, |k| k, |k| k).with_stats(&mut stats);
+            assert_eq!( Some((2, 2)), rows.next() );
+        }
+        assert_eq!( JoinStats { left_advanced: 1, right_advanced: 1, matched: 1 }, stats );
+    }
+
+    #[test]
+    fn with_positions_counts_skipped_items() {
+        let v = vec![0, 1, 3, 5, 6];
+        let w = vec![2, 3, 3, 4, 6];
+
+        let mut rows = v.join(w, |k| k, |k| k).with_positions();
+        assert_eq!( Some((2, 3, 1, 3)), rows.next() );
+        assert_eq!( (3, 4), (rows.left_pos(), rows.right_pos()) );
+        assert_eq!( vec![(2, 3, 2, 3), (4, 6, 4, 6)], rows.by_ref().collect::<Vec<_>>() );
+        assert_eq!( (5, 5), (rows.left_pos(), rows.right_pos()) );
+    }
+
+    #[test]
+    #[should_panic(expected = "already been advanced")]
+    fn with_positions_of_an_advanced_join() {
+        let mut join_it = vec![1, 2].join(vec![1, 2], |k| k, |k| k);
+        join_it.next();
+
+        join_it.with_positions();
+    }
+
+    #[test]
+    fn join_it_observed_reports_every_skip() {
+        let v = vec![0, 1, 1, 3, 4, 6];
+        let w = vec![1, 2, 2, 4, 5, 7];
+
+        let mut rows = 0;
+        let mut events = Vec::new();
+        join_it_observed(v.clone(), w.clone(), |k| k, |k| k, |_,_| rows += 1, |event| events.push(event));
+        assert_eq!( 3, rows );
+        assert_eq!( vec![
+            AdvanceEvent::Left(0),
+            AdvanceEvent::Right(2),
+            AdvanceEvent::Right(2),
+            AdvanceEvent::Left(3),
+            AdvanceEvent::Right(5),
+            AdvanceEvent::Left(6),
+        ], events );
+
+        let mut stats = JoinStats::default();
+        v.join(w, |k| k, |k| k).with_stats(&mut stats).for_each(drop);
+        assert_eq!( stats.left_advanced, events.iter().filter(|e| matches!(e, AdvanceEvent::Left(_))).count() );
+        assert_eq!( stats.right_advanced, events.iter().filter(|e| matches!(e, AdvanceEvent::Right(_))).count() );
+    }
+}
@@ -0,0 +1,303 @@
+//! Inner join of sorted asynchronous streams.
+
+use alloc::vec::Vec;
+use core::cmp::{Ord, Ordering};
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
+
+
+/// Inner join between two sorted streams, created by `async_join`.
+///
+/// Works like `JoinIt`, buffering the right items sharing a key to pair them with every left item
+/// of that key. The streams have to be `Unpin`, pin them with `Box::pin` or `pin!` otherwise.
+pub struct AsyncJoin<S, T, KI, KJ> where
+    S: Stream,
+    T: Stream
+{
+    i: S,
+    j: T,
+    ki: KI,
+    kj: KJ,
+    curr_i: Option<S::Item>,
+    curr_j: Option<T::Item>,
+    group: Vec<T::Item>,
+    pos: usize,
+    filling: bool,
+    done_i: bool,
+    done_j: bool,
+    done: bool,
+}
+
+
+/// Joins the sorted streams `i` and `j`, based on the key extractors `ki` and `kj`, into a stream
+/// of the joined rows.
+///
+/// ```
+/// extern crate futures;
+/// extern crate join_it;
+///
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use join_it::async_join;
+///
+/// let v = stream::iter(vec![(0,'a'),(1,'b'),(2,'c')]);
+/// let w = stream::iter(vec![(1,11),(2,22),(3,33)]);
+///
+/// let rows = block_on(async_join(v, w, |(k,_)| k, |(k,_)| k).collect::<Vec<_>>());
+/// assert_eq!(vec![((1,'b'),(1,11)), ((2,'c'),(2,22))], rows);
+/// ```
+pub fn async_join<S,T,KI,KJ,K>(i: S, j: T, ki: KI, kj: KJ) -> AsyncJoin<S,T,KI,KJ> where
+    S: Stream + Unpin,
+    T: Stream + Unpin,
+    S::Item: Clone,
+    T::Item: Clone,
+    KI: FnMut(S::Item) -> K,
+    KJ: FnMut(T::Item) -> K,
+    K: Ord
+{
+    AsyncJoin {
+        i,
+        j,
+        ki,
+        kj,
+        curr_i: None,
+        curr_j: None,
+        group: Vec::new(),
+        pos: 0,
+        filling: false,
+        done_i: false,
+        done_j: false,
+        done: false,
+    }
+}
+
+
+impl<S,T,KI,KJ> Unpin for AsyncJoin<S,T,KI,KJ> where
+    S: Stream + Unpin,
+    T: Stream + Unpin
+{}
+
+
+impl<S,T,KI,KJ,K> AsyncJoin<S,T,KI,KJ> where
+    S: Stream + Unpin,
+    T: Stream + Unpin,
+    S::Item: Clone,
+    T::Item: Clone,
+    KI: FnMut(S::Item) -> K,
+    KJ: FnMut(T::Item) -> K,
+    K: Ord
+{
+    /// Polls for the next row, leaving `done` to the caller.
+    fn poll_row(&mut self, cx: &mut Context) -> Poll<Option<(S::Item, T::Item)>> {
+        loop {
+            if self.filling {
+                match Pin::new(&mut self.j).poll_next(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Some(w)) => {
+                        if (self.kj)(w.clone()) == (self.kj)(self.group[0].clone()) {
+                            self.group.push(w);
+                        } else {
+                            self.curr_j = Some(w);
+                            self.filling = false;
+                        }
+                    },
+                    Poll::Ready(None) => {
+                        self.done_j = true;
+                        self.filling = false;
+                    },
+                }
+                continue;
+            }
+
+            if self.curr_i.is_none() && !self.done_i {
+                match Pin::new(&mut self.i).poll_next(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Some(v)) => self.curr_i = Some(v),
+                    Poll::Ready(None) => self.done_i = true,
+                }
+            }
+            let v = match self.curr_i.take() {
+                Some(v) => v,
+                None => return Poll::Ready(None),
+            };
+
+            if !self.group.is_empty() {
+                if Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(self.group[0].clone())) == Ordering::Equal {
+                    let w = self.group[self.pos].clone();
+                    self.pos += 1;
+                    if self.pos < self.group.len() {
+                        self.curr_i = Some(v.clone());
+                    } else {
+                        self.pos = 0;
+                    }
+                    return Poll::Ready(Some((v, w)));
+                }
+                self.group.clear();
+            }
+
+            if self.curr_j.is_none() && !self.done_j {
+                match Pin::new(&mut self.j).poll_next(cx) {
+                    Poll::Pending => {
+                        self.curr_i = Some(v);
+                        return Poll::Pending;
+                    },
+                    Poll::Ready(Some(w)) => self.curr_j = Some(w),
+                    Poll::Ready(None) => self.done_j = true,
+                }
+            }
+            let w = match self.curr_j.take() {
+                Some(w) => w,
+                None => return Poll::Ready(None),
+            };
+
+            match Ord::cmp(&(self.ki)(v.clone()), &(self.kj)(w.clone())) {
+                Ordering::Less => self.curr_j = Some(w),
+                Ordering::Greater => self.curr_i = Some(v),
+                Ordering::Equal => {
+                    self.group.push(w);
+                    self.filling = true;
+                    self.curr_i = Some(v);
+                },
+            }
+        }
+    }
+}
+
+
+impl<S,T,KI,KJ,K> Stream for AsyncJoin<S,T,KI,KJ> where
+    S: Stream + Unpin,
+    T: Stream + Unpin,
+    S::Item: Clone,
+    T::Item: Clone,
+    KI: FnMut(S::Item) -> K,
+    KJ: FnMut(T::Item) -> K,
+    K: Ord
+{
+    type Item = (S::Item, T::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let row = this.poll_row(cx);
+        if let Poll::Ready(None) = row {
+            this.done = true;
+            this.group.clear();
+        }
+        row
+    }
+}
+
+
+impl<S,T,KI,KJ,K> FusedStream for AsyncJoin<S,T,KI,KJ> where
+    S: Stream + Unpin,
+    T: Stream + Unpin,
+    S::Item: Clone,
+    T::Item: Clone,
+    KI: FnMut(S::Item) -> K,
+    KJ: FnMut(T::Item) -> K,
+    K: Ord
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+
+impl<S,T,KI,KJ> fmt::Debug for AsyncJoin<S,T,KI,KJ> where
+    S: Stream + fmt::Debug,
+    T: Stream + fmt::Debug,
+    S::Item: fmt::Debug,
+    T::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncJoin")
+            .field("i", &self.i)
+            .field("j", &self.j)
+            .field("ki", &"<key_fn>")
+            .field("kj", &"<key_fn>")
+            .field("curr_i", &self.curr_i)
+            .field("curr_j", &self.curr_j)
+            .field("group", &self.group)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use futures::executor::block_on;
+    use futures::stream::{self, FusedStream, Stream, StreamExt};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Returns `Pending` before every item of the wrapped stream, waking the task right away.
+    struct Stutter<S> {
+        stream: S,
+        ready: bool,
+    }
+
+    impl<S: Stream + Unpin> Stream for Stutter<S> {
+        type Item = S::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<S::Item>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            Pin::new(&mut self.stream).poll_next(cx)
+        }
+    }
+
+    fn stutter<T>(v: Vec<T>) -> Stutter<stream::Iter<std::vec::IntoIter<T>>> {
+        Stutter { stream: stream::iter(v), ready: false }
+    }
+
+    #[test]
+    fn async_join_matches_join() {
+        let v = vec![(0,'a'), (1,'b'), (1,'c'), (2,'d'), (4,'e')];
+        let w = vec![(1,10), (1,11), (2,20), (3,30), (4,40), (5,50)];
+
+        let expected = v.clone().join(w.clone(), |(k,_)| k, |(k,_)| k).collect::<Vec<_>>();
+        let rows = block_on(async_join(stream::iter(v), stream::iter(w), |(k,_)| k, |(k,_)| k).collect::<Vec<_>>());
+        assert_eq!( expected, rows );
+    }
+
+    #[test]
+    fn async_join_pending_streams() {
+        let v = vec![1, 2, 2, 3, 5, 5];
+        let w = vec![0, 2, 2, 2, 5, 6];
+
+        let expected = v.clone().join(w.clone(), |k| k, |k| k).collect::<Vec<_>>();
+        let rows = block_on(async_join(stutter(v), stutter(w), |k| k, |k| k).collect::<Vec<_>>());
+        assert_eq!( 8, rows.len() );
+        assert_eq!( expected, rows );
+    }
+
+    #[test]
+    fn async_join_polls_only_the_side_behind() {
+        let v = vec![1, 3, 3];
+        let endless = stream::iter(0u32..).inspect(|&k| assert!( k <= 4 ));
+
+        let rows = block_on(async_join(stutter(v), endless, |k| k, |k| k).collect::<Vec<_>>());
+        assert_eq!( vec![(1, 1), (3, 3), (3, 3)], rows );
+    }
+
+    #[test]
+    fn async_join_with_an_empty_side() {
+        let empty: Vec<u32> = vec![];
+
+        let mut join = async_join(stream::iter(empty), stream::iter(vec![1, 2]), |k| k, |k| k);
+        assert_eq!( None, block_on(join.next()) );
+        assert!( join.is_terminated() );
+        assert_eq!( None, block_on(join.next()) );
+    }
+}